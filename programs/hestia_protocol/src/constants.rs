@@ -2,14 +2,16 @@ use anchor_lang::declare_id;
 
 pub const ED25519_PROGRAM_ID: &str = "Ed25519SigVerify111111111111111111111111111";
 
+// Customers can reclaim an escrowed payment once the order has not been completed for this long
+pub const ORDER_ESCROW_TIMEOUT: i64 = 2 * 60 * 60;
+
+// Tax and service charge rates are expressed in basis points of the subtotal
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
 pub mod admin_wallet {
     use super::*;
     declare_id!("6KuX26FZqzqpsHDLfkXoBXbQRPEDEbstqNiPBKHNJQ9e");
 }
-pub mod signing_authority {
-    use super::*;
-    declare_id!("S1GvFEzpWUM5EwYZMFLcEdMEXEjhUZzhhQeN5AvG6mw");
-}
 pub mod membership_wallet {
     use super::*;
     declare_id!("BDEECMrE5dv4cc5na6Fi8sNkfzYxckd6ZjsuEzp7hXnJ");
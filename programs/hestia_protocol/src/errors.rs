@@ -18,7 +18,21 @@ pub enum SetupError {
     #[msg("You cannot remove the primary admin")]
     CannotRemovePrimaryAdmin,
     #[msg("Employee does not belong here")]
-    EmployeeMismatch
+    EmployeeMismatch,
+    #[msg("The payment signer list is full")]
+    TooManyPaymentSigners,
+    #[msg("The payment signer label is too long")]
+    PaymentSignerLabelTooLong,
+    #[msg("The payment signer is already registered")]
+    PaymentSignerExists,
+    #[msg("The payment signer is not registered")]
+    PaymentSignerNotFound,
+    #[msg("Rates can't exceed 10000 basis points")]
+    InvalidRate,
+    #[msg("UTC offset must be between -12:00 and +14:00")]
+    InvalidUtcOffset,
+    #[msg("The account doesn't have the legacy layout")]
+    NotALegacyAccount,
 }
 
 #[error_code]
@@ -66,7 +80,21 @@ pub enum BuyingError {
     #[msg("Invalid instruction")]
     InvalidInstruction,
     #[msg("Insufficient Points")]
-    InsufficientPoints
+    InsufficientPoints,
+    #[msg("The signed payment does not match this order")]
+    PaymentMessageMismatch,
+    #[msg("The signed payment has expired")]
+    PaymentMessageExpired,
+    #[msg("The payment receipt account is missing")]
+    MissingPaymentReceipt,
+    #[msg("Payment accounts for the restaurant currency are missing")]
+    MissingPaymentAccounts,
+    #[msg("The payment currency does not match the restaurant currency")]
+    InvalidCurrency,
+    #[msg("The payout account does not belong to the recipient wallet")]
+    InvalidPayoutDestination,
+    #[msg("The amount exceeds the restaurant's balance above its rent-exempt minimum")]
+    InsufficientRevenue,
 }
 
 #[error_code]
@@ -81,6 +109,42 @@ pub enum OrderError {
     InvalidStatusType,
     #[msg("The order does not belong to the correct restaurant")]
     InvalidRestaurant,
+    #[msg("The order total does not match the menu item prices")]
+    TotalMismatch,
+    #[msg("This menu item is not currently available")]
+    InactiveMenuItem,
+    #[msg("The order has no items")]
+    EmptyOrder,
+    #[msg("Order line quantity must be greater than zero")]
+    InvalidQuantity,
+    #[msg("The order cannot move to the requested status")]
+    InvalidStatusTransition,
+    #[msg("Orders must be cancelled through the cancel order instruction")]
+    CancelRequiresCancelOrder,
+    #[msg("Orders must be refunded through the refund order instruction")]
+    RefundRequiresRefundOrder,
+    #[msg("The refund amount exceeds what is left to refund")]
+    InvalidRefundAmount,
+    #[msg("Escrowed orders must be paid with tokens")]
+    EscrowRequiresTokenPayment,
+    #[msg("The order escrow account is missing")]
+    MissingEscrow,
+    #[msg("Split shares must be at least two positive amounts adding up to the balance due")]
+    InvalidSplit,
+    #[msg("Split bills can only be paid on-chain")]
+    SplitRequiresOnChainPayment,
+    #[msg("Share does not exist on this order")]
+    InvalidShare,
+    #[msg("Share has already been paid")]
+    ShareAlreadyPaid,
+    #[msg("This wallet has already paid a share of the order")]
+    PayerAlreadyPaid,
+    #[msg("Every share of the bill must be paid first")]
+    OrderNotSettled,
+    #[msg("Payer accounts are missing or invalid")]
+    InvalidPayerAccounts,
+    #[msg("The order customer must be the signer")]
+    CustomerMismatch,
 }
 
 #[error_code]
@@ -91,6 +155,10 @@ pub enum RewardError {
     InvalidRewardAuthority,
     #[msg("Invalid category")]
     InvalidCategory,
+    #[msg("The reward voucher belongs to another restaurant")]
+    InvalidRestaurant,
+    #[msg("The voucher is not an active legacy reward voucher")]
+    NotALegacyVoucher,
 }
 
 #[error_code]
@@ -99,4 +167,180 @@ pub enum MenuError {
     Unauthorized,
     #[msg("Invalid SKU provided")]
     InvalidSku,
-}
\ No newline at end of file
+    #[msg("Menu item does not match the order line")]
+    InvalidMenuItem,
+    #[msg("The menu item's price schedule account is missing")]
+    MissingPriceSchedule,
+    #[msg("Invalid price schedule window")]
+    InvalidPriceWindow,
+    #[msg("Too many price schedule windows")]
+    TooManyPriceWindows,
+    #[msg("The menu item already has the maximum number of modifier groups")]
+    TooManyModifierGroups,
+    #[msg("Invalid modifier group")]
+    InvalidModifierGroup,
+    #[msg("Modifier group accounts are missing or out of order")]
+    MissingModifierGroup,
+    #[msg("Selected modifier does not exist")]
+    InvalidModifier,
+    #[msg("Modifier selections are outside the group's min/max")]
+    InvalidModifierSelection,
+    #[msg("Only menu items in the Combo category can have component slots")]
+    NotACombo,
+    #[msg("Invalid combo slots")]
+    InvalidComboSlots,
+    #[msg("Combo accounts are missing or out of order")]
+    MissingCombo,
+    #[msg("Combo component is invalid")]
+    InvalidComboComponent,
+    #[msg("Substitution does not exist or repeats a slot")]
+    InvalidSubstitution,
+    #[msg("Deactivate the menu item before removing it")]
+    MenuItemActive,
+    #[msg("An active reward voucher still references this menu item")]
+    MenuItemHasVouchers,
+    #[msg("Remove the price schedule, modifier groups and combo slots first")]
+    MenuItemHasDependents,
+    #[msg("The menu item is still a component or substitute of a combo")]
+    MenuItemInCombo,
+    #[msg("Menu section names must be between 1 and 32 bytes")]
+    InvalidMenuSection,
+    #[msg("The menu already has the maximum number of sections")]
+    TooManyMenuSections,
+    #[msg("The menu section already has the maximum number of items")]
+    TooManyMenuEntries,
+    #[msg("The menu item is already in this section")]
+    DuplicateMenuEntry,
+    #[msg("Menu section not found")]
+    MissingMenuSection,
+    #[msg("Menu item not found in this section")]
+    MissingMenuEntry,
+    #[msg("Position is outside the menu section")]
+    InvalidMenuPosition,
+    #[msg("The menu item already exists, update it instead")]
+    MenuItemExists,
+}
+
+#[error_code]
+pub enum InventoryError {
+    #[msg("Not enough stock to fulfill this order")]
+    InsufficientStock,
+    #[msg("Ingredient accounts are missing")]
+    MissingIngredientAccounts,
+    #[msg("Ingredient list does not belong to this menu item")]
+    InvalidIngredientList,
+    #[msg("Inventory item does not match the ingredient list")]
+    InventoryItemMismatch,
+    #[msg("Par level can't be below the reorder threshold")]
+    InvalidReorderLevels,
+    #[msg("Auto-deactivate is not enabled for this inventory item")]
+    AutoDeactivateDisabled,
+    #[msg("The inventory item still covers this menu item")]
+    NotDepleted,
+    #[msg("Menu item accounts are missing or invalid")]
+    InvalidMenuItemAccounts,
+    #[msg("Lots need a positive quantity and an expiry in the future")]
+    InvalidLot,
+    #[msg("The inventory item already tracks the maximum number of lots")]
+    TooManyLots,
+    #[msg("The inventory item already exists, update it instead")]
+    InventoryItemExists,
+    #[msg("No lot of this inventory item has expired")]
+    NothingExpired,
+    #[msg("Only the owner and employees above TeamMember can record waste")]
+    UnauthorizedWaste,
+    #[msg("Waste quantity must be greater than zero")]
+    InvalidWasteQuantity,
+    #[msg("You are not authorized to perform this stock count action")]
+    UnauthorizedStockCount,
+    #[msg("The stock count is no longer accepting counts")]
+    StockCountClosed,
+    #[msg("The stock count has already been finalized")]
+    StockCountFinalized,
+    #[msg("Stock count record accounts are missing or invalid")]
+    InvalidStockCountAccounts,
+    #[msg("This count has already been applied to the stock")]
+    StockCountApplied,
+}
+
+#[error_code]
+pub enum TipError {
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    #[msg("Tip pool accounts are missing")]
+    MissingTipPool,
+    #[msg("Tips can only be paid on-chain")]
+    TipRequiresOnChainPayment,
+    #[msg("Invalid tip distribution")]
+    InvalidDistribution,
+    #[msg("Tip pool does not hold enough to cover this amount")]
+    InsufficientTipPool,
+    #[msg("Employee accounts are missing or invalid")]
+    InvalidEmployeeAccounts,
+    #[msg("Employee does not belong to this restaurant")]
+    EmployeeRestaurantMismatch,
+    #[msg("Total share weight must be greater than zero")]
+    ZeroShares,
+    #[msg("The employee is already clocked in")]
+    AlreadyClockedIn,
+    #[msg("The employee is not clocked in")]
+    NotClockedIn,
+    #[msg("Every on-shift employee must share the distribution")]
+    MissingOnShiftEmployees,
+    #[msg("The employee is not on shift")]
+    NotOnShift,
+    #[msg("A tip distribution round is in progress")]
+    DistributionInProgress,
+    #[msg("The shift is already tallied for this round")]
+    AlreadyTallied,
+    #[msg("The shift is not awaiting a payout from this round")]
+    NotTallied,
+}
+
+#[error_code]
+pub enum PromotionError {
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    #[msg("Promo codes must be between 1 and 16 bytes")]
+    InvalidCode,
+    #[msg("Invalid promotion rule")]
+    InvalidRule,
+    #[msg("Promotions must end after they start")]
+    InvalidSchedule,
+    #[msg("Invalid category")]
+    InvalidCategory,
+    #[msg("Promotion accounts are missing")]
+    MissingPromotion,
+    #[msg("This promotion is not active")]
+    PromotionInactive,
+    #[msg("This promotion has not started yet")]
+    PromotionNotStarted,
+    #[msg("This promotion has ended")]
+    PromotionExpired,
+    #[msg("This promotion has been fully redeemed")]
+    MaxRedemptionsReached,
+    #[msg("You have already redeemed this promotion the maximum number of times")]
+    CustomerLimitReached,
+    #[msg("This promotion does not apply to any item in the order")]
+    PromotionNotApplicable,
+}
+
+#[error_code]
+pub enum SupplierError {
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    #[msg("Supplier names must be between 1 and 32 bytes")]
+    InvalidName,
+    #[msg("This supplier is not active")]
+    InactiveSupplier,
+    #[msg("Purchase orders need between 1 and 16 lines with a positive quantity")]
+    InvalidPurchaseOrder,
+    #[msg("Inventory item accounts are missing or don't belong to the restaurant")]
+    InvalidInventoryAccounts,
+    #[msg("Received quantities must match the lines and not exceed what is outstanding")]
+    InvalidReceipt,
+    #[msg("The purchase order has already been fully received")]
+    AlreadyReceived,
+    #[msg("Nothing received on this purchase order is left to pay")]
+    NothingToPay,
+}
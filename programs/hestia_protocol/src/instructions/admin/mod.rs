@@ -5,4 +5,7 @@ pub mod remove_admin;
 pub use remove_admin::*;
 
 pub mod toggle_protocol;
-pub use toggle_protocol::*;
\ No newline at end of file
+pub use toggle_protocol::*;
+
+pub mod payment_signers;
+pub use payment_signers::*;
\ No newline at end of file
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::state::{PaymentConfig, PaymentSigner};
+use crate::errors::SetupError;
+use crate::constants::admin_wallet as ADMIN;
+
+/*
+    Manage Payment Signers Instruction
+
+    Security checks:
+    - Verify that the account interacting with this instruction is the admin of the entire protocol.
+    - Labels are capped so the PaymentConfig account never needs to be resized.
+
+    Functionality:
+    - Initializes the PaymentConfig account the first time a signer is added.
+    - Adds a new payment-attestation signer (e.g. the off-chain Stripe signer).
+    - Rotates a signer to a new key, keeping its label and active flag.
+    - Revokes a signer so its attestations are no longer accepted.
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AddPaymentSignerArgs {
+    key: Pubkey,
+    label: String,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RotatePaymentSignerArgs {
+    key: Pubkey,
+    new_key: Pubkey,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RevokePaymentSignerArgs {
+    key: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ManagePaymentSigner<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = PaymentConfig::INIT_SPACE,
+        seeds = [b"payment_config"],
+        bump,
+    )]
+    pub payment_config: Account<'info, PaymentConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ManagePaymentSigner<'info> {
+    /// Registers a new active payment signer
+    pub fn add_signer(&mut self, args: AddPaymentSignerArgs, bump: u8) -> Result<()> {
+        require!(args.label.len() <= PaymentSigner::MAX_LABEL_LEN, SetupError::PaymentSignerLabelTooLong);
+        require!(
+            !self.payment_config.signers.iter().any(|signer| signer.key == args.key),
+            SetupError::PaymentSignerExists
+        );
+        require!(
+            self.payment_config.signers.len() < PaymentConfig::MAX_SIGNERS,
+            SetupError::TooManyPaymentSigners
+        );
+
+        self.payment_config.bump = bump;
+        self.payment_config.signers.push(PaymentSigner {
+            key: args.key,
+            label: args.label,
+            active: true,
+        });
+
+        Ok(())
+    }
+
+    /// Replaces a signer's key, keeping its label and active flag
+    pub fn rotate_signer(&mut self, args: RotatePaymentSignerArgs) -> Result<()> {
+        require!(
+            !self.payment_config.signers.iter().any(|signer| signer.key == args.new_key),
+            SetupError::PaymentSignerExists
+        );
+
+        let signer = self.payment_config.signers
+            .iter_mut()
+            .find(|signer| signer.key == args.key)
+            .ok_or(SetupError::PaymentSignerNotFound)?;
+        signer.key = args.new_key;
+
+        Ok(())
+    }
+
+    /// Deactivates a signer so its attestations are rejected
+    pub fn revoke_signer(&mut self, args: RevokePaymentSignerArgs) -> Result<()> {
+        let signer = self.payment_config.signers
+            .iter_mut()
+            .find(|signer| signer.key == args.key)
+            .ok_or(SetupError::PaymentSignerNotFound)?;
+        signer.active = false;
+
+        Ok(())
+    }
+}
+
+/// Handler for adding a payment signer
+pub fn add_payment_signer_handler(ctx: Context<ManagePaymentSigner>, args: AddPaymentSignerArgs) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ADMIN::id(), SetupError::Unauthorized);
+
+    ctx.accounts.add_signer(args, ctx.bumps.payment_config)
+}
+
+/// Handler for rotating a payment signer's key
+pub fn rotate_payment_signer_handler(ctx: Context<ManagePaymentSigner>, args: RotatePaymentSignerArgs) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ADMIN::id(), SetupError::Unauthorized);
+
+    ctx.accounts.rotate_signer(args)
+}
+
+/// Handler for revoking a payment signer
+pub fn revoke_payment_signer_handler(ctx: Context<ManagePaymentSigner>, args: RevokePaymentSignerArgs) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ADMIN::id(), SetupError::Unauthorized);
+
+    ctx.accounts.revoke_signer(args)
+}
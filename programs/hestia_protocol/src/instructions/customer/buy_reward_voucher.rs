@@ -8,8 +8,8 @@ use mpl_core::{
     instructions::CreateV1CpiBuilder
 };
 use crate::{
-    state::{RewardVoucher, CompletedRewardVoucher, Customer, Restaurant, Manager, Protocol},
-    errors::BuyingError
+    state::{RewardVoucher, CompletedRewardVoucher, Customer, MenuItem, Restaurant, Manager, Protocol},
+    errors::{BuyingError, RewardError}
 };
 
 /*
@@ -19,10 +19,12 @@ use crate::{
     - Allows a customer to purchase a reward voucher using their reward points.
     - Creates a new asset (voucher) for the customer using the MPL Core program.
     - Updates the voucher state (completes it if all shares are sold).
+    - A completed voucher no longer holds its menu item, which can then be removed.
 
     Security checks:
     - Ensures the customer has enough reward points to purchase the voucher.
     - Verifies that the reward's update authority matches the manager's key.
+    - Ensures the voucher belongs to the restaurant, so its SKU resolves to that restaurant's menu item.
 */
 
 #[derive(Accounts)]
@@ -47,8 +49,15 @@ pub struct BuyRewardVoucher<'info> {
     #[account(
         seeds = [b"voucher", reward.key().as_ref()],
         bump,
+        constraint = voucher.restaurant == restaurant.key() @ RewardError::InvalidRestaurant,
     )] 
     pub voucher: Account<'info, RewardVoucher>,
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), voucher.item_sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
     #[account(mut)] 
     pub customer_voucher: Signer<'info>,
     #[account(
@@ -71,7 +80,9 @@ impl<'info> BuyRewardVoucher<'info> {
         );
 
         // Deduct points from customer
-        self.customer.reward_points -= self.voucher.price;
+        self.customer.reward_points = self.customer.reward_points
+            .checked_sub(self.voucher.price)
+            .ok_or(BuyingError::InsufficientPoints)?;
 
         // Create the voucher asset
         self.create_voucher_asset(&uri)?;
@@ -101,10 +112,15 @@ impl<'info> BuyRewardVoucher<'info> {
     }
 
     fn update_voucher_state(&mut self) -> Result<()> {
-        if self.voucher.share_sold + 1 == self.voucher.share {
+        let share_sold = self.voucher.share_sold
+            .checked_add(1)
+            .ok_or(BuyingError::Overflow)?;
+
+        if share_sold == self.voucher.share {
+            self.menu_item.active_vouchers = self.menu_item.active_vouchers.saturating_sub(1);
             self.complete_voucher()?;
         } else {
-            self.voucher.share_sold += 1;
+            self.voucher.share_sold = share_sold;
         }
         Ok(())
     }
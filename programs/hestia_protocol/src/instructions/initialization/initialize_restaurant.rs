@@ -22,6 +22,8 @@ pub struct CreateRestaurantArgs {
     symbol: String,
     currency: Pubkey,
     url: String,
+    reward_points_per_token: u64,
+    utc_offset_minutes: i16,
     bump: u8,
 }
 
@@ -63,6 +65,11 @@ impl<'info> CreateRestaurant<'info> {
             currency: args.currency,
             url: args.url,
             customer_count: 0,
+            reward_points_per_token: args.reward_points_per_token,
+            tax_bps: 0,
+            category_tax_bps: [None; 7],
+            service_charge_bps: 0,
+            utc_offset_minutes: args.utc_offset_minutes,
             bump
         });
         Ok(())
@@ -0,0 +1,105 @@
+use anchor_lang::{prelude::*, Discriminator};
+use crate::{
+    state::{AdminProfile, Restaurant, RestaurantType},
+    errors::SetupError,
+    utils::resize_program_account,
+};
+
+/*
+    Migrate Restaurant Instruction
+
+    Functionality:
+    - Converts a Restaurant account created before the reward rate, tax, service charge and UTC offset
+      fields were added to the current layout
+    - Resizes the account in place, the restaurant admin pays the additional rent
+    - Keeps the legacy reward of 1 point per whole token spent, no tax or service charge and UTC local time;
+      they can be changed afterwards with the usual instructions
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - The restaurant is derived from the signer and must belong to it
+    - The account must be a Restaurant owned by this program with the legacy size, so it can't be migrated twice
+*/
+
+// Legacy Restaurant: restaurant_type, owner, name, symbol, currency, url, customer_count, bump
+const LEGACY_RESTAURANT_SPACE: usize = 8 + 4 + 4 + 32 + 32 + 4 + 32 + 32 + 4 + 8 + 1;
+
+#[derive(AnchorDeserialize)]
+struct LegacyRestaurant {
+    restaurant_type: RestaurantType,
+    owner: Pubkey,
+    name: String,
+    symbol: String,
+    currency: Pubkey,
+    url: String,
+    customer_count: u64,
+    bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRestaurant<'info> {
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: Legacy Restaurant layout, deserialized in the instruction
+    pub restaurant: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateRestaurant<'info> {
+    pub fn migrate_restaurant(&mut self) -> Result<()> {
+        let legacy = {
+            let data = self.restaurant.try_borrow_data()?;
+            require!(
+                data.len() == LEGACY_RESTAURANT_SPACE && data[..8] == Restaurant::DISCRIMINATOR,
+                SetupError::NotALegacyAccount
+            );
+            LegacyRestaurant::deserialize(&mut &data[8..]).map_err(|_| SetupError::NotALegacyAccount)?
+        };
+        require_keys_eq!(legacy.owner, self.restaurant_admin.key(), SetupError::Unauthorized);
+
+        let restaurant = Restaurant {
+            restaurant_type: legacy.restaurant_type,
+            owner: legacy.owner,
+            name: legacy.name,
+            symbol: legacy.symbol,
+            currency: legacy.currency,
+            url: legacy.url,
+            customer_count: legacy.customer_count,
+            reward_points_per_token: 1,
+            tax_bps: 0,
+            category_tax_bps: [None; 7],
+            service_charge_bps: 0,
+            utc_offset_minutes: 0,
+            bump: legacy.bump,
+        };
+
+        let restaurant_info = self.restaurant.to_account_info();
+        resize_program_account(
+            &restaurant_info,
+            Restaurant::INIT_SPACE,
+            &self.restaurant_admin.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+        restaurant.try_serialize(&mut &mut restaurant_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateRestaurant>) -> Result<()> {
+    ctx.accounts.migrate_restaurant()
+}
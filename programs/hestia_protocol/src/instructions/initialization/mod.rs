@@ -2,4 +2,16 @@ pub mod initialize_protocol;
 pub use initialize_protocol::*;
 
 pub mod initialize_restaurant;
-pub use initialize_restaurant::*;
\ No newline at end of file
+pub use initialize_restaurant::*;
+
+pub mod update_tax_config;
+pub use update_tax_config::*;
+
+pub mod update_utc_offset;
+pub use update_utc_offset::*;
+
+pub mod migrate_restaurant;
+pub use migrate_restaurant::*;
+
+pub mod withdraw_revenue;
+pub use withdraw_revenue::*;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Restaurant},
+    errors::SetupError,
+    constants::BPS_DENOMINATOR,
+};
+
+/*
+    Update Tax Config Instruction
+
+    Functionality:
+    - Sets the restaurant's sales tax rate, in basis points of the subtotal
+    - Optionally overrides the rate per MenuCategoryType (e.g. a higher rate for Alcohol),
+      indexed in MenuCategoryType order with None falling back to the restaurant rate
+    - Sets an optional service charge, also in basis points of the subtotal (0 to disable)
+    - New rates apply to orders placed afterwards, existing orders keep their recorded amounts
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Rejects any rate above 10000 basis points
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateTaxConfigArgs {
+    tax_bps: u16,
+    category_tax_bps: [Option<u16>; 7],
+    service_charge_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaxConfig<'info> {
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump = restaurant.bump,
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> UpdateTaxConfig<'info> {
+    pub fn update_tax_config(&mut self, args: UpdateTaxConfigArgs) -> Result<()> {
+        let valid_rate = |bps: u16| bps as u64 <= BPS_DENOMINATOR;
+        require!(
+            valid_rate(args.tax_bps)
+                && valid_rate(args.service_charge_bps)
+                && args.category_tax_bps.iter().flatten().all(|bps| valid_rate(*bps)),
+            SetupError::InvalidRate
+        );
+
+        self.restaurant.tax_bps = args.tax_bps;
+        self.restaurant.category_tax_bps = args.category_tax_bps;
+        self.restaurant.service_charge_bps = args.service_charge_bps;
+
+        emit!(TaxConfigUpdated {
+            restaurant: self.restaurant.key(),
+            tax_bps: args.tax_bps,
+            category_tax_bps: args.category_tax_bps,
+            service_charge_bps: args.service_charge_bps,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateTaxConfig>, args: UpdateTaxConfigArgs) -> Result<()> {
+    ctx.accounts.update_tax_config(args)
+}
+
+// Event emitted when a restaurant changes its tax or service charge rates
+#[event]
+pub struct TaxConfigUpdated {
+    pub restaurant: Pubkey,
+    pub tax_bps: u16,
+    pub category_tax_bps: [Option<u16>; 7],
+    pub service_charge_bps: u16,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Restaurant},
+    errors::SetupError,
+};
+
+/*
+    Update UTC Offset Instruction
+
+    Functionality:
+    - Sets the restaurant's UTC offset in minutes, used to evaluate menu price schedules in local time
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Rejects offsets outside of -12:00 to +14:00
+*/
+
+#[derive(Accounts)]
+pub struct UpdateUtcOffset<'info> {
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump = restaurant.bump,
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> UpdateUtcOffset<'info> {
+    pub fn update_utc_offset(&mut self, utc_offset_minutes: i16) -> Result<()> {
+        require!((-12 * 60..=14 * 60).contains(&utc_offset_minutes), SetupError::InvalidUtcOffset);
+
+        self.restaurant.utc_offset_minutes = utc_offset_minutes;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateUtcOffset>, utc_offset_minutes: i16) -> Result<()> {
+    ctx.accounts.update_utc_offset(utc_offset_minutes)
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Restaurant},
+    errors::{BuyingError, SetupError},
+    utils::{is_native_sol, transfer_program_lamports},
+};
+
+/*
+    Withdraw Revenue Instruction
+
+    Functionality:
+    - Transfers native SOL revenue held by the Restaurant account to the restaurant admin
+    - The Restaurant account always keeps its rent-exempt minimum
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - The restaurant is derived from the signer and must belong to it
+    - Only restaurants paid in native SOL hold their revenue in the Restaurant account
+*/
+
+#[derive(Accounts)]
+pub struct WithdrawRevenue<'info> {
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump = restaurant.bump,
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> WithdrawRevenue<'info> {
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(is_native_sol(&self.restaurant.currency), BuyingError::InvalidCurrency);
+
+        let restaurant_info = self.restaurant.to_account_info();
+        let available = restaurant_info.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(restaurant_info.data_len()));
+        require!(amount > 0 && amount <= available, BuyingError::InsufficientRevenue);
+
+        transfer_program_lamports(
+            &restaurant_info,
+            &self.restaurant_admin.to_account_info(),
+            amount,
+        )?;
+
+        emit!(RevenueWithdrawn {
+            restaurant: self.restaurant.key(),
+            amount,
+            destination: self.restaurant_admin.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
+    ctx.accounts.withdraw(amount)
+}
+
+// Event emitted when the restaurant admin withdraws native SOL revenue
+#[event]
+pub struct RevenueWithdrawn {
+    pub restaurant: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
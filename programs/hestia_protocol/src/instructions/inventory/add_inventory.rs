@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::{state::{AdminProfile, Restaurant, InventoryCategoryType, InventoryItem}, errors::SetupError};
+use crate::{
+    state::{AdminProfile, Restaurant, InventoryCategoryType, InventoryItem},
+    errors::{InventoryError, SetupError},
+};
 
 /*
     Add/Update Inventory Instruction
@@ -7,10 +10,16 @@ use crate::{state::{AdminProfile, Restaurant, InventoryCategoryType, InventoryIt
     Functionality:
     - Allows a restaurant admin to add a new inventory item or update an existing one.
     - Creates or updates an InventoryItem account with the provided details.
+    - Sets the reorder threshold and par level, and whether dependent menu items are deactivated once it runs out.
+    - Stock is only set when the item is added, as untracked stock (not part of a lot). Updates keep the stock,
+      quantities change through received lots, recorded waste, orders and finalized stock counts so lots and
+      stock stay consistent.
 
     Security checks:
     - Ensures the signer is the restaurant admin.
     - Verifies that the restaurant belongs to the admin.
+    - The par level can't be below the reorder threshold.
+    - An existing item can only be updated, adding it again is rejected so its stock and lots are kept.
 */
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -20,6 +29,9 @@ pub struct InventoryArgs {
     name: String,
     price: u64,
     stock: u64,
+    reorder_threshold: u64,
+    par_level: u64,
+    auto_deactivate: bool,
     initialized: bool,
 }
 
@@ -54,6 +66,9 @@ pub struct ManageInventory<'info> {
 
 impl<'info> ManageInventory<'info> {
     pub fn add_inventory(&mut self, category: InventoryCategoryType, args: InventoryArgs, bump: u8) -> Result<()> {
+        // Adding over an existing item would wipe its stock and lots, it has to be updated instead
+        require!(!self.item.initialized, InventoryError::InventoryItemExists);
+
         self.item.set_inner(InventoryItem {
             sku: args.sku,
             category,
@@ -61,6 +76,10 @@ impl<'info> ManageInventory<'info> {
             price: args.price,
             stock: args.stock,
             last_order: Clock::get()?.unix_timestamp,
+            reorder_threshold: args.reorder_threshold,
+            par_level: args.par_level,
+            auto_deactivate: args.auto_deactivate,
+            lots: Vec::new(),
             initialized: true,
             bump,
         });
@@ -69,9 +88,12 @@ impl<'info> ManageInventory<'info> {
     }
 
     pub fn update_inventory(&mut self, args: InventoryArgs) -> Result<()> {
-        self.item.stock = args.stock;
+        // args.stock is ignored, overwriting it would bypass the lots and the stock count variance records
         self.item.price = args.price;
         self.item.last_order = Clock::get()?.unix_timestamp;
+        self.item.reorder_threshold = args.reorder_threshold;
+        self.item.par_level = args.par_level;
+        self.item.auto_deactivate = args.auto_deactivate;
 
         Ok(())
     }
@@ -80,6 +102,7 @@ impl<'info> ManageInventory<'info> {
 pub fn handler(ctx: Context<ManageInventory>, args: InventoryArgs) -> Result<()> {
     let category = InventoryCategoryType::from_u8(args.category)
         .ok_or(SetupError::InvalidObjectType)?;
+    require!(args.par_level >= args.reorder_threshold, InventoryError::InvalidReorderLevels);
 
     if args.initialized {
         ctx.accounts.update_inventory(args)
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{IngredientList, InventoryItem, MenuItem, Restaurant},
+    errors::{InventoryError, MenuError},
+    instructions::MenuItemToggled,
+};
+
+/*
+    Deactivate Dependent Menu Items Instruction
+
+    Functionality:
+    - Disables the menu items whose IngredientList depends on an inventory item that ran out
+    - Permissionless, so an off-chain keeper can react to LowStock events; every condition is checked on-chain
+    - Emits MenuItemToggled for each deactivated menu item, the restaurant admin re-enables them once restocked
+
+    Security checks:
+    - The inventory item must belong to the restaurant and have auto_deactivate enabled
+    - Each menu item must belong to the restaurant and list the inventory item in its IngredientList
+    - The remaining stock must not cover a single unit of the menu item
+
+    Remaining accounts:
+    - [menu_item (writable), ingredient_list] pairs, one per menu item to deactivate
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct DeactivateDependentItemsArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: DeactivateDependentItemsArgs)]
+pub struct DeactivateDependentItems<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump = item.bump,
+        constraint = item.auto_deactivate @ InventoryError::AutoDeactivateDisabled,
+    )]
+    pub item: Account<'info, InventoryItem>,
+
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> DeactivateDependentItems<'info> {
+    pub fn deactivate(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 2 == 0,
+            InventoryError::InvalidMenuItemAccounts
+        );
+
+        for accounts in remaining_accounts.chunks(2) {
+            let mut menu_item = Account::<MenuItem>::try_from(&accounts[0])?;
+            let ingredient_list = Account::<IngredientList>::try_from(&accounts[1])?;
+            require_keys_eq!(menu_item.restaurant, self.restaurant.key(), MenuError::InvalidMenuItem);
+            require_keys_eq!(ingredient_list.menu_item, menu_item.key(), InventoryError::InvalidIngredientList);
+
+            let per_unit = ingredient_list.ingredients
+                .iter()
+                .find(|(inventory_key, _)| *inventory_key == self.item.key())
+                .map(|(_, per_unit)| *per_unit)
+                .ok_or(InventoryError::InventoryItemMismatch)?;
+            require!(self.item.stock < per_unit, InventoryError::NotDepleted);
+
+            if menu_item.active {
+                menu_item.active = false;
+                menu_item.exit(&crate::ID)?;
+
+                emit!(MenuItemToggled {
+                    sku: menu_item.sku.clone(),
+                    new_status: false,
+                    restaurant: self.restaurant.key(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DeactivateDependentItems<'info>>, _args: DeactivateDependentItemsArgs) -> Result<()> {
+    ctx.accounts.deactivate(ctx.remaining_accounts)
+}
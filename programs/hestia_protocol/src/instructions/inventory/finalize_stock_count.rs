@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, EmployeeType, InventoryItem, Restaurant, StockCount, StockCountRecord, StockCountStatus},
+    errors::{BuyingError, InventoryError},
+    utils::check_low_stock,
+};
+
+/*
+    Finalize Stock Count Instruction
+
+    Functionality:
+    - Applies the variance of each StockCountRecord passed to its InventoryItem and marks the record applied
+    - The variance is applied to the current stock, so sales made since the count was submitted are kept
+    - Shortages are consumed from lots first-in-first-out, surpluses are added as untracked stock
+    - Large sessions can be finalized over several calls; the first call locks the session against new counts
+      and the session is Finalized once every record has been applied
+    - Emits StockCountAdjusted per record, LowStock for every item lowered below its reorder threshold
+      and StockCountFinalized once the session is complete
+
+    Security checks:
+    - Ensures the signer is a Manager or Director of the restaurant
+    - The session must belong to the restaurant and not be finalized yet
+    - Every record must belong to the session, not be applied yet, and come with its inventory item
+
+    Remaining accounts:
+    - [record (writable), inventory_item (writable)] pairs, one per record to apply
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct FinalizeStockCountArgs {
+    id: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: FinalizeStockCountArgs)]
+pub struct FinalizeStockCount<'info> {
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+        constraint = employee.employee_type >= EmployeeType::Manager @ InventoryError::UnauthorizedStockCount,
+    )]
+    pub employee: Account<'info, Employee>,
+
+    #[account(
+        mut,
+        seeds = [b"stock_count", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump = stock_count.bump,
+        constraint = stock_count.status != StockCountStatus::Finalized @ InventoryError::StockCountFinalized,
+    )]
+    pub stock_count: Account<'info, StockCount>,
+}
+
+impl<'info> FinalizeStockCount<'info> {
+    pub fn finalize(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(remaining_accounts.len() % 2 == 0, InventoryError::InvalidStockCountAccounts);
+
+        // Counts submitted after adjustments start being applied would be reconciled against moved stock
+        self.stock_count.status = StockCountStatus::Finalizing;
+
+        for accounts in remaining_accounts.chunks(2) {
+            let mut record = Account::<StockCountRecord>::try_from(&accounts[0])?;
+            require_keys_eq!(record.stock_count, self.stock_count.key(), InventoryError::InvalidStockCountAccounts);
+            require_keys_eq!(record.inventory_item, accounts[1].key(), InventoryError::InvalidStockCountAccounts);
+            require!(!record.applied, InventoryError::StockCountApplied);
+
+            let mut inventory_item = Account::<InventoryItem>::try_from(&accounts[1])?;
+            let previous_stock = inventory_item.stock;
+            let adjustment = record.variance.unsigned_abs();
+            if record.variance < 0 {
+                // Stock sold since the count may already cover part of the shortage
+                inventory_item.consume(adjustment.min(previous_stock));
+            } else {
+                inventory_item.stock = previous_stock.checked_add(adjustment).ok_or(BuyingError::Overflow)?;
+            }
+            check_low_stock(&inventory_item, previous_stock, &self.restaurant.key());
+            inventory_item.exit(&crate::ID)?;
+
+            record.applied = true;
+            record.exit(&crate::ID)?;
+            self.stock_count.applied = self.stock_count.applied.checked_add(1).ok_or(BuyingError::Overflow)?;
+
+            emit!(StockCountAdjusted {
+                stock_count: self.stock_count.key(),
+                inventory_item: inventory_item.key(),
+                sku: inventory_item.sku.clone(),
+                variance: record.variance,
+                previous_stock,
+                stock: inventory_item.stock,
+                restaurant: self.restaurant.key(),
+            });
+        }
+
+        if self.stock_count.applied == self.stock_count.records {
+            let now = Clock::get()?.unix_timestamp;
+            self.stock_count.status = StockCountStatus::Finalized;
+            self.stock_count.finalized_by = Some(self.signer.key());
+            self.stock_count.finalized_at = Some(now);
+
+            emit!(StockCountFinalized {
+                stock_count: self.stock_count.key(),
+                id: self.stock_count.id,
+                records: self.stock_count.records,
+                finalized_by: self.signer.key(),
+                finalized_at: now,
+                restaurant: self.restaurant.key(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeStockCount<'info>>, _args: FinalizeStockCountArgs) -> Result<()> {
+    ctx.accounts.finalize(ctx.remaining_accounts)
+}
+
+// Event emitted when a counted variance is applied to an inventory item's stock
+#[event]
+pub struct StockCountAdjusted {
+    pub stock_count: Pubkey,
+    pub inventory_item: Pubkey,
+    pub sku: String,
+    pub variance: i64,
+    pub previous_stock: u64,
+    pub stock: u64,
+    pub restaurant: Pubkey,
+}
+
+// Event emitted once every record of a stock count has been applied
+#[event]
+pub struct StockCountFinalized {
+    pub stock_count: Pubkey,
+    pub id: u64,
+    pub records: u16,
+    pub finalized_by: Pubkey,
+    pub finalized_at: i64,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::{prelude::*, Discriminator};
+use crate::{
+    state::{AdminProfile, InventoryCategoryType, InventoryItem, Restaurant},
+    errors::SetupError,
+    utils::resize_program_account,
+};
+
+/*
+    Migrate Inventory Item Instruction
+
+    Functionality:
+    - Converts an InventoryItem created before reorder levels and lots were added to the current layout
+    - Resizes the account in place, the restaurant admin pays the additional rent
+    - The migrated item has no reorder threshold, par level or lots, its whole stock is untracked;
+      reorder levels can be set afterwards with restaurant_add_inventory_item
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The account must be the restaurant's InventoryItem for the SKU with the legacy size, so it can't be migrated twice
+*/
+
+// Legacy InventoryItem: sku, category, name, price, stock, last_order, initialized, bump
+const LEGACY_INVENTORY_ITEM_SPACE: usize = 8 + 8 + 32 + 4 + 8 + 8 + 8 + 1;
+
+#[derive(AnchorDeserialize)]
+struct LegacyInventoryItem {
+    sku: String,
+    category: InventoryCategoryType,
+    name: String,
+    price: u64,
+    stock: u64,
+    last_order: i64,
+    initialized: bool,
+    bump: u8,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MigrateInventoryItemArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MigrateInventoryItemArgs)]
+pub struct MigrateInventoryItem<'info> {
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump,
+    )]
+    /// CHECK: Legacy InventoryItem layout, deserialized in the instruction
+    pub item: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateInventoryItem<'info> {
+    pub fn migrate_inventory_item(&mut self) -> Result<()> {
+        let legacy = {
+            let data = self.item.try_borrow_data()?;
+            require!(data[..8] == InventoryItem::DISCRIMINATOR, SetupError::NotALegacyAccount);
+            let legacy = LegacyInventoryItem::deserialize(&mut &data[8..])
+                .map_err(|_| SetupError::NotALegacyAccount)?;
+            require!(
+                data.len() == LEGACY_INVENTORY_ITEM_SPACE + legacy.sku.len() + legacy.name.len(),
+                SetupError::NotALegacyAccount
+            );
+            legacy
+        };
+
+        let item = InventoryItem {
+            sku: legacy.sku,
+            category: legacy.category,
+            name: legacy.name,
+            price: legacy.price,
+            stock: legacy.stock,
+            last_order: legacy.last_order,
+            reorder_threshold: 0,
+            par_level: 0,
+            auto_deactivate: false,
+            lots: Vec::new(),
+            initialized: legacy.initialized,
+            bump: legacy.bump,
+        };
+
+        let item_info = self.item.to_account_info();
+        resize_program_account(
+            &item_info,
+            InventoryItem::INIT_SPACE + item.sku.len() + item.name.len(),
+            &self.restaurant_admin.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+        item.try_serialize(&mut &mut item_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateInventoryItem>, _args: MigrateInventoryItemArgs) -> Result<()> {
+    ctx.accounts.migrate_inventory_item()
+}
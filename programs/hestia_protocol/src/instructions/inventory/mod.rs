@@ -2,4 +2,28 @@ pub mod add_inventory;
 pub use add_inventory::*;
 
 pub mod remove_inventory;
-pub use remove_inventory::*;
\ No newline at end of file
+pub use remove_inventory::*;
+
+pub mod deactivate_dependent_items;
+pub use deactivate_dependent_items::*;
+
+pub mod receive_inventory_lot;
+pub use receive_inventory_lot::*;
+
+pub mod write_off_expired_lots;
+pub use write_off_expired_lots::*;
+
+pub mod record_waste;
+pub use record_waste::*;
+
+pub mod open_stock_count;
+pub use open_stock_count::*;
+
+pub mod submit_stock_count;
+pub use submit_stock_count::*;
+
+pub mod finalize_stock_count;
+pub use finalize_stock_count::*;
+
+pub mod migrate_inventory_item;
+pub use migrate_inventory_item::*;
\ No newline at end of file
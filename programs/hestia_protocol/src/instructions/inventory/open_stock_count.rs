@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, EmployeeType, Restaurant, StockCount, StockCountStatus},
+    errors::InventoryError,
+};
+
+/*
+    Open Stock Count Instruction
+
+    Functionality:
+    - Opens a physical stock-count session in which employees submit the quantities they counted
+    - Counts are reconciled against the expected stock and only applied once the session is finalized
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or a Manager (or above) of the restaurant
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct OpenStockCountArgs {
+    id: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: OpenStockCountArgs)]
+pub struct OpenStockCount<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = StockCount::INIT_SPACE,
+        seeds = [b"stock_count", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stock_count: Account<'info, StockCount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenStockCount<'info> {
+    pub fn open_stock_count(&mut self, id: u64, bump: u8) -> Result<()> {
+        let is_manager = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type >= EmployeeType::Manager);
+        require!(self.signer.key() == self.restaurant.owner || is_manager, InventoryError::UnauthorizedStockCount);
+
+        self.stock_count.set_inner(StockCount {
+            restaurant: self.restaurant.key(),
+            id,
+            status: StockCountStatus::Open,
+            records: 0,
+            applied: 0,
+            opened_by: self.signer.key(),
+            opened_at: Clock::get()?.unix_timestamp,
+            finalized_by: None,
+            finalized_at: None,
+            bump,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<OpenStockCount>, args: OpenStockCountArgs) -> Result<()> {
+    ctx.accounts.open_stock_count(args.id, ctx.bumps.stock_count)
+}
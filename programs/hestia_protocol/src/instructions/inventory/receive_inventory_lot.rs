@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Restaurant, InventoryItem},
+    errors::SetupError,
+    utils::receive_lot,
+};
+
+/*
+    Receive Inventory Lot Instruction
+
+    Functionality:
+    - Allows a restaurant admin to record a batch received outside of a purchase order.
+    - Adds the batch to the InventoryItem as its newest lot with its unit cost, received time and expiry,
+      and increments the stock.
+    - A batch with the same unit cost and expiry as the newest lot is merged into it, so repeated deliveries
+      don't use up the item's lots.
+    - Lots are consumed first-in-first-out by every instruction that lowers the stock.
+
+    Security checks:
+    - Ensures the signer is the restaurant admin.
+    - Verifies that the restaurant belongs to the admin.
+    - The lot needs a positive quantity and, when set, an expiry in the future.
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ReceiveInventoryLotArgs {
+    sku: String,
+    quantity: u64,
+    unit_cost: u64,
+    expires_at: Option<i64>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ReceiveInventoryLotArgs)]
+pub struct ReceiveInventoryLot<'info> {
+    #[account(
+        mut,
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump = item.bump,
+    )]
+    pub item: Account<'info, InventoryItem>,
+
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> ReceiveInventoryLot<'info> {
+    pub fn receive_inventory_lot(&mut self, args: ReceiveInventoryLotArgs) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        receive_lot(&mut self.item, args.quantity, args.unit_cost, args.expires_at, now)
+    }
+}
+
+pub fn handler(ctx: Context<ReceiveInventoryLot>, args: ReceiveInventoryLotArgs) -> Result<()> {
+    ctx.accounts.receive_inventory_lot(args)
+}
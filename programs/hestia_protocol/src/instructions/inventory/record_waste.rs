@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, EmployeeType, InventoryItem, Restaurant, WasteLog, WasteReason},
+    errors::{BuyingError, InventoryError, SetupError},
+    utils::check_low_stock,
+};
+
+/*
+    Record Waste Instruction
+
+    Functionality:
+    - Removes stock that was thrown away rather than sold, with a reason (spoiled, dropped, comped, theft)
+    - Values the waste at cost: lots are consumed first-in-first-out at their unit cost, untracked stock at InventoryItem.price
+    - Adds the cost to the restaurant's WasteLog totals, created on first use
+    - Emits WasteRecorded, and LowStock when the stock drops below the reorder threshold
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or an employee above TeamMember of the restaurant
+    - The inventory item must belong to the restaurant and hold at least the wasted quantity
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RecordWasteArgs {
+    sku: String,
+    quantity: u64,
+    reason: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RecordWasteArgs)]
+pub struct RecordWaste<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        mut,
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump = item.bump,
+    )]
+    pub item: Account<'info, InventoryItem>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = WasteLog::INIT_SPACE,
+        seeds = [b"waste_log", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub waste_log: Account<'info, WasteLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordWaste<'info> {
+    pub fn record_waste(&mut self, reason: WasteReason, quantity: u64, bump: u8) -> Result<()> {
+        // Team members can't write stock off on their own
+        let is_authorized = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type > EmployeeType::TeamMember);
+        require!(self.signer.key() == self.restaurant.owner || is_authorized, InventoryError::UnauthorizedWaste);
+        require!(quantity > 0, InventoryError::InvalidWasteQuantity);
+
+        let previous_stock = self.item.stock;
+        let cost = self.item.consume(quantity).ok_or(InventoryError::InsufficientStock)?;
+
+        self.waste_log.restaurant = self.restaurant.key();
+        self.waste_log.bump = bump;
+        self.waste_log.record(&reason, cost).ok_or(BuyingError::Overflow)?;
+
+        emit!(WasteRecorded {
+            inventory_item: self.item.key(),
+            sku: self.item.sku.clone(),
+            reason,
+            quantity,
+            cost,
+            recorded_by: self.signer.key(),
+            restaurant: self.restaurant.key(),
+        });
+        check_low_stock(&self.item, previous_stock, &self.restaurant.key());
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RecordWaste>, args: RecordWasteArgs) -> Result<()> {
+    let reason = WasteReason::from_u8(args.reason)
+        .ok_or(SetupError::InvalidObjectType)?;
+
+    ctx.accounts.record_waste(reason, args.quantity, ctx.bumps.waste_log)
+}
+
+// Expired is reserved for the expired lots write-off
+impl WasteReason {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Spoiled),
+            1 => Some(Self::Dropped),
+            2 => Some(Self::Comped),
+            3 => Some(Self::Theft),
+            _ => None,
+        }
+    }
+}
+
+// Event emitted when an employee records wasted stock
+#[event]
+pub struct WasteRecorded {
+    pub inventory_item: Pubkey,
+    pub sku: String,
+    pub reason: WasteReason,
+    pub quantity: u64,
+    pub cost: u64, // in currency base units
+    pub recorded_by: Pubkey,
+    pub restaurant: Pubkey,
+}
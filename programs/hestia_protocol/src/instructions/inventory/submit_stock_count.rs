@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, InventoryItem, Restaurant, StockCount, StockCountRecord, StockCountStatus},
+    errors::{BuyingError, InventoryError},
+};
+
+/*
+    Submit Stock Count Instruction
+
+    Functionality:
+    - Records the quantity counted for an InventoryItem in an open stock-count session
+    - Stores the expected stock at submission time and the variance (counted - expected) in a StockCountRecord
+    - Submitting again for the same item replaces the previous count
+    - The stock itself is left untouched until the session is finalized
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or an employee of the restaurant
+    - The session must belong to the restaurant and still be open
+    - The inventory item must belong to the restaurant
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SubmitStockCountArgs {
+    id: u64,
+    sku: String,
+    counted: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: SubmitStockCountArgs)]
+pub struct SubmitStockCount<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        mut,
+        seeds = [b"stock_count", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump = stock_count.bump,
+        constraint = stock_count.status == StockCountStatus::Open @ InventoryError::StockCountClosed,
+    )]
+    pub stock_count: Account<'info, StockCount>,
+
+    #[account(
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump = item.bump,
+    )]
+    pub item: Account<'info, InventoryItem>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = StockCountRecord::INIT_SPACE,
+        seeds = [b"stock_count_record", stock_count.key().as_ref(), item.key().as_ref()],
+        bump,
+    )]
+    pub record: Account<'info, StockCountRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitStockCount<'info> {
+    pub fn submit_stock_count(&mut self, counted: u64, bump: u8) -> Result<()> {
+        require!(
+            self.signer.key() == self.restaurant.owner || self.employee.is_some(),
+            InventoryError::UnauthorizedStockCount
+        );
+
+        // A new record for this session, a resubmission keeps the count
+        if self.record.stock_count == Pubkey::default() {
+            self.stock_count.records = self.stock_count.records.checked_add(1).ok_or(BuyingError::Overflow)?;
+        }
+
+        let expected = self.item.stock;
+        let variance = i64::try_from(counted as i128 - expected as i128).map_err(|_| BuyingError::Overflow)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        self.record.set_inner(StockCountRecord {
+            stock_count: self.stock_count.key(),
+            inventory_item: self.item.key(),
+            counted,
+            expected,
+            variance,
+            counted_by: self.signer.key(),
+            counted_at: now,
+            applied: false,
+            bump,
+        });
+
+        emit!(StockCounted {
+            stock_count: self.stock_count.key(),
+            inventory_item: self.item.key(),
+            sku: self.item.sku.clone(),
+            counted,
+            expected,
+            variance,
+            counted_by: self.signer.key(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SubmitStockCount>, args: SubmitStockCountArgs) -> Result<()> {
+    ctx.accounts.submit_stock_count(args.counted, ctx.bumps.record)
+}
+
+// Event emitted when an employee submits a count for an inventory item
+#[event]
+pub struct StockCounted {
+    pub stock_count: Pubkey,
+    pub inventory_item: Pubkey,
+    pub sku: String,
+    pub counted: u64,
+    pub expected: u64,
+    pub variance: i64,
+    pub counted_by: Pubkey,
+    pub restaurant: Pubkey,
+}
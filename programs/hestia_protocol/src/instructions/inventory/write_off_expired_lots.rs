@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{InventoryItem, Restaurant, WasteLog, WasteReason},
+    errors::{BuyingError, InventoryError},
+    utils::check_low_stock,
+};
+
+/*
+    Write Off Expired Lots Instruction
+
+    Functionality:
+    - Removes every expired lot of an inventory item and deducts it from the stock
+    - Permissionless, expiry is checked against the cluster clock so an off-chain keeper can crank it
+    - Emits InventoryWasted with the quantity and the cost of the written-off lots, and adds the cost to the
+      restaurant's WasteLog under WasteReason::Expired
+    - Emits LowStock when the write-off lowers the stock below the reorder threshold
+
+    Security checks:
+    - The inventory item must belong to the restaurant
+    - At least one lot must have expired
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct WriteOffExpiredLotsArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: WriteOffExpiredLotsArgs)]
+pub struct WriteOffExpiredLots<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"inventory", restaurant.key().as_ref(), args.sku.as_ref()],
+        bump = item.bump,
+    )]
+    pub item: Account<'info, InventoryItem>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = WasteLog::INIT_SPACE,
+        seeds = [b"waste_log", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub waste_log: Account<'info, WasteLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WriteOffExpiredLots<'info> {
+    pub fn write_off(&mut self, bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let previous_stock = self.item.stock;
+        let lots = self.item.lots.len();
+
+        let (quantity, cost) = self.item.remove_expired_lots(now);
+        require!(quantity > 0, InventoryError::NothingExpired);
+
+        self.waste_log.restaurant = self.restaurant.key();
+        self.waste_log.bump = bump;
+        self.waste_log.record(&WasteReason::Expired, cost).ok_or(BuyingError::Overflow)?;
+
+        emit!(InventoryWasted {
+            inventory_item: self.item.key(),
+            sku: self.item.sku.clone(),
+            quantity,
+            cost,
+            lots: (lots - self.item.lots.len()) as u8,
+            restaurant: self.restaurant.key(),
+        });
+        check_low_stock(&self.item, previous_stock, &self.restaurant.key());
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WriteOffExpiredLots>, _args: WriteOffExpiredLotsArgs) -> Result<()> {
+    ctx.accounts.write_off(ctx.bumps.waste_log)
+}
+
+// Event emitted when expired lots are written off
+#[event]
+pub struct InventoryWasted {
+    pub inventory_item: Pubkey,
+    pub sku: String,
+    pub quantity: u64,
+    pub cost: u64, // in currency base units, at each lot's unit cost
+    pub lots: u8, // number of lots written off
+    pub restaurant: Pubkey,
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::{
     state::{AdminProfile, MenuCategoryType, Menu, MenuItem, Restaurant, IngredientList},
-    errors::SetupError
+    errors::{MenuError, SetupError}
 };
 
 /*
@@ -16,6 +16,8 @@ use crate::{
     - Ensures the signer is the restaurant admin
     - Verifies that the restaurant belongs to the admin
     - Uses PDAs to ensure proper ownership and access control
+    - An existing menu item can only be updated, adding it again is rejected so its vouchers,
+      combo references, modifier groups, combo slots and price schedule are kept
 
     Note: This instruction handles both adding new menu items and updating existing ones.
 */
@@ -41,7 +43,7 @@ pub struct ManageMenuItem<'info> {
         init_if_needed,
         payer = restaurant_admin,
         space = MenuItem::INIT_SPACE + args.sku.len() + args.name.len() + args.description.len(),
-        seeds = [b"menu_item", args.sku.as_bytes()],
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
         bump,
     )] 
     pub menu_item: Account<'info, MenuItem>,
@@ -50,7 +52,7 @@ pub struct ManageMenuItem<'info> {
     #[account(
         init_if_needed,
         payer = restaurant_admin,
-        space = IngredientList::INIT_SPACE + (args.ingredients.len() * 32),
+        space = IngredientList::INIT_SPACE + (args.ingredients.len() * (32 + 8)),
         seeds = [b"ingredient_list", menu_item.key().as_ref()],
         bump,
     )]
@@ -89,23 +91,33 @@ pub struct ManageMenuItem<'info> {
 
 impl<'info> ManageMenuItem<'info> {
     /// Adds a new menu item
-    pub fn add_menu_item(&mut self, category: MenuCategoryType, args: MenuItemArgs, menu_item_bump: u8, ingredient_list_bump: u8 ) -> Result<()> {
+    pub fn add_menu_item(&mut self, category: MenuCategoryType, args: MenuItemArgs, menu_bump: u8, menu_item_bump: u8, ingredient_list_bump: u8 ) -> Result<()> {
+        // Adding over an existing item would reset its references, it has to be updated instead
+        require!(self.menu_item.restaurant == Pubkey::default(), MenuError::MenuItemExists);
+
         // Initialize the menu if it hasn't been initialized yet
         if !self.menu.initialized {
             self.menu.set_inner(Menu {
-                bump: menu_item_bump,
+                bump: menu_bump,
                 initialized: true,
+                sections: Vec::new(),
             });
         }
 
         // Set the menu item details
         self.menu_item.set_inner(MenuItem {
+            restaurant: self.restaurant.key(),
             sku: args.sku.clone(),
             category,
             name: args.name,
             price: args.price,
             description: args.description,
             active: args.active,
+            price_schedule: None,
+            modifier_groups: Vec::new(),
+            combo: None,
+            active_vouchers: 0,
+            combo_references: 0,
             bump: menu_item_bump,
         });
 
@@ -121,6 +133,12 @@ impl<'info> ManageMenuItem<'info> {
 
     /// Updates an existing menu item
     pub fn update_menu_item(&mut self, category: MenuCategoryType, args: MenuItemArgs) -> Result<()> {
+        // Combo slots only make sense on combo items, they must be removed first
+        require!(
+            self.menu_item.combo.is_none() || category == MenuCategoryType::Combo,
+            MenuError::NotACombo
+        );
+
         // Update the menu item details
         self.menu_item.category = category;
         self.menu_item.name = args.name;
@@ -141,7 +159,7 @@ pub fn add_menu_item_handler(ctx: Context<ManageMenuItem>, args: MenuItemArgs) -
     let category = MenuCategoryType::_from_u8(args.category)
         .ok_or(SetupError::InvalidObjectType)?;
 
-    ctx.accounts.add_menu_item(category, args, bumps.menu_item, bumps.ingredient_list)
+    ctx.accounts.add_menu_item(category, args, bumps.menu, bumps.menu_item, bumps.ingredient_list)
 }
 
 /// Handler for updating an existing menu item
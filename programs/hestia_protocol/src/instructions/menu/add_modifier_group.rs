@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, MenuItem, ModifierGroup, ModifierOption, Restaurant},
+    errors::{MenuError, SetupError},
+};
+
+/*
+    Add Modifier Group Instruction
+
+    Functionality:
+    - Creates a ModifierGroup (e.g. "Size", "Extra toppings") attached to a menu item
+    - Each option carries a price delta and the inventory it consumes per unit ordered
+    - min_selections / max_selections bound how many options an order line may pick from the group
+    - Links the group to the menu item so every order line of the item is validated against it
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Validates the name, options and selection bounds, and the number of groups per menu item
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AddModifierGroupArgs {
+    sku: String,
+    name: String,
+    min_selections: u8,
+    max_selections: u8,
+    options: Vec<ModifierOption>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: AddModifierGroupArgs)]
+pub struct AddModifierGroup<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        init,
+        payer = restaurant_admin,
+        space = ModifierGroup::INIT_SPACE
+            + args.name.len()
+            + args.options.iter().map(|option| option.space()).sum::<usize>(),
+        seeds = [b"modifier_group", menu_item.key().as_ref(), args.name.as_bytes()],
+        bump,
+    )]
+    pub modifier_group: Account<'info, ModifierGroup>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddModifierGroup<'info> {
+    pub fn add_modifier_group(&mut self, args: AddModifierGroupArgs, bump: u8) -> Result<()> {
+        require!(
+            self.menu_item.modifier_groups.len() < MenuItem::MAX_MODIFIER_GROUPS,
+            MenuError::TooManyModifierGroups
+        );
+
+        let valid_options = !args.options.is_empty()
+            && args.options.len() <= ModifierGroup::MAX_OPTIONS
+            && args.options.iter().all(|option| {
+                !option.name.is_empty() && option.name.len() <= ModifierGroup::MAX_NAME_LEN
+            });
+        require!(
+            !args.name.is_empty()
+                && args.name.len() <= ModifierGroup::MAX_NAME_LEN
+                && valid_options
+                && args.max_selections >= 1
+                && args.min_selections <= args.max_selections
+                && args.max_selections as usize <= args.options.len(),
+            MenuError::InvalidModifierGroup
+        );
+
+        self.modifier_group.set_inner(ModifierGroup {
+            menu_item: self.menu_item.key(),
+            name: args.name,
+            min_selections: args.min_selections,
+            max_selections: args.max_selections,
+            options: args.options,
+            bump,
+        });
+        self.menu_item.modifier_groups.push(self.modifier_group.key());
+
+        emit!(ModifierGroupAdded {
+            sku: self.menu_item.sku.clone(),
+            modifier_group: self.modifier_group.key(),
+            name: self.modifier_group.name.clone(),
+            min_selections: self.modifier_group.min_selections,
+            max_selections: self.modifier_group.max_selections,
+            options: self.modifier_group.options.clone(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AddModifierGroup>, args: AddModifierGroupArgs) -> Result<()> {
+    ctx.accounts.add_modifier_group(args, ctx.bumps.modifier_group)
+}
+
+// Event emitted when a modifier group is attached to a menu item
+#[event]
+pub struct ModifierGroupAdded {
+    pub sku: String,
+    pub modifier_group: Pubkey,
+    pub name: String,
+    pub min_selections: u8,
+    pub max_selections: u8,
+    pub options: Vec<ModifierOption>,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Menu, MenuItem, MenuSection, Restaurant},
+    errors::{MenuError, SetupError},
+    utils::resize_program_account,
+};
+
+/*
+    Manage Menu Sections Instruction
+
+    Functionality:
+    - Adds a menu item to a named section of the restaurant's Menu, creating the section when needed
+    - Moves an entry within a section, or a whole section within the menu, to a new display position
+    - Removes an entry from a section, dropping the section once it is empty
+    - Resizes the Menu account to fit its sections, the restaurant admin pays or gets back the rent difference
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Only menu items of the restaurant can be added, at most once per section
+    - Section names, section count and entries per section are capped
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AddMenuEntryArgs {
+    section: String,
+    position: Option<u16>, // appended when not set
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MoveMenuEntryArgs {
+    section: Option<String>, // moves whole sections when not set
+    from: u16,
+    to: u16,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemoveMenuEntryArgs {
+    section: String,
+    menu_item: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ManageMenuSection<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu", restaurant.key().as_ref()],
+        bump = menu.bump,
+    )]
+    pub menu: Account<'info, Menu>,
+
+    #[account(
+        constraint = menu_item.restaurant == restaurant.key() @ MenuError::InvalidMenuItem,
+    )]
+    pub menu_item: Option<Account<'info, MenuItem>>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ManageMenuSection<'info> {
+    pub fn add_entry(&mut self, args: AddMenuEntryArgs) -> Result<()> {
+        let menu_item = self.menu_item.as_ref().ok_or(MenuError::InvalidMenuItem)?.key();
+
+        let index = match self.menu.sections.iter().position(|section| section.name == args.section) {
+            Some(index) => index,
+            None => {
+                require!(
+                    !args.section.is_empty() && args.section.len() <= MenuSection::MAX_NAME_LEN,
+                    MenuError::InvalidMenuSection
+                );
+                require!(self.menu.sections.len() < Menu::MAX_SECTIONS, MenuError::TooManyMenuSections);
+                self.menu.sections.push(MenuSection { name: args.section.clone(), items: Vec::new() });
+                self.menu.sections.len() - 1
+            }
+        };
+
+        let section = &mut self.menu.sections[index];
+        require!(section.items.len() < MenuSection::MAX_ITEMS, MenuError::TooManyMenuEntries);
+        require!(!section.items.contains(&menu_item), MenuError::DuplicateMenuEntry);
+
+        let position = args.position.map_or(section.items.len(), |position| position as usize);
+        require!(position <= section.items.len(), MenuError::InvalidMenuPosition);
+        section.items.insert(position, menu_item);
+
+        self.resize_menu()?;
+
+        emit!(MenuEntryAdded {
+            section: args.section,
+            menu_item,
+            position: position as u16,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn move_entry(&mut self, args: MoveMenuEntryArgs) -> Result<()> {
+        let (from, to) = (args.from as usize, args.to as usize);
+
+        match args.section.as_ref() {
+            Some(name) => {
+                let section = self.menu.sections
+                    .iter_mut()
+                    .find(|section| section.name == *name)
+                    .ok_or(MenuError::MissingMenuSection)?;
+                require!(from < section.items.len() && to < section.items.len(), MenuError::InvalidMenuPosition);
+                let menu_item = section.items.remove(from);
+                section.items.insert(to, menu_item);
+            },
+            None => {
+                let sections = &mut self.menu.sections;
+                require!(from < sections.len() && to < sections.len(), MenuError::InvalidMenuPosition);
+                let section = sections.remove(from);
+                sections.insert(to, section);
+            },
+        }
+
+        emit!(MenuEntryMoved {
+            section: args.section,
+            from: args.from,
+            to: args.to,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_entry(&mut self, args: RemoveMenuEntryArgs) -> Result<()> {
+        let index = self.menu.sections
+            .iter()
+            .position(|section| section.name == args.section)
+            .ok_or(MenuError::MissingMenuSection)?;
+
+        let section = &mut self.menu.sections[index];
+        let position = section.items
+            .iter()
+            .position(|menu_item| *menu_item == args.menu_item)
+            .ok_or(MenuError::MissingMenuEntry)?;
+        section.items.remove(position);
+
+        if section.items.is_empty() {
+            self.menu.sections.remove(index);
+        }
+
+        self.resize_menu()?;
+
+        emit!(MenuEntryRemoved {
+            section: args.section,
+            menu_item: args.menu_item,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    fn resize_menu(&self) -> Result<()> {
+        resize_program_account(
+            &self.menu.to_account_info(),
+            self.menu.space(),
+            &self.restaurant_admin.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
+}
+
+pub fn add_menu_entry_handler(ctx: Context<ManageMenuSection>, args: AddMenuEntryArgs) -> Result<()> {
+    ctx.accounts.add_entry(args)
+}
+
+pub fn move_menu_entry_handler(ctx: Context<ManageMenuSection>, args: MoveMenuEntryArgs) -> Result<()> {
+    ctx.accounts.move_entry(args)
+}
+
+pub fn remove_menu_entry_handler(ctx: Context<ManageMenuSection>, args: RemoveMenuEntryArgs) -> Result<()> {
+    ctx.accounts.remove_entry(args)
+}
+
+// Event emitted when a menu item is added to a menu section
+#[event]
+pub struct MenuEntryAdded {
+    pub section: String,
+    pub menu_item: Pubkey,
+    pub position: u16,
+    pub restaurant: Pubkey,
+}
+
+// Event emitted when a menu entry, or a whole section when `section` is not set, is moved
+#[event]
+pub struct MenuEntryMoved {
+    pub section: Option<String>,
+    pub from: u16,
+    pub to: u16,
+    pub restaurant: Pubkey,
+}
+
+// Event emitted when a menu item is removed from a menu section
+#[event]
+pub struct MenuEntryRemoved {
+    pub section: String,
+    pub menu_item: Pubkey,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::{prelude::*, Discriminator};
+use crate::{
+    state::{AdminProfile, Menu, Restaurant},
+    errors::SetupError,
+    utils::resize_program_account,
+};
+
+/*
+    Migrate Menu Instruction
+
+    Functionality:
+    - Converts a Menu account created before menu sections were added to the current layout, with no sections
+    - Resizes the account in place, the restaurant admin pays the additional rent
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The account must be the restaurant's Menu with the legacy size, so it can't be migrated twice
+*/
+
+// Legacy Menu: initialized, bump
+const LEGACY_MENU_SPACE: usize = 8 + 4 + 1;
+
+#[derive(AnchorDeserialize)]
+struct LegacyMenu {
+    initialized: bool,
+    bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMenu<'info> {
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"menu", restaurant.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: Legacy Menu layout, deserialized in the instruction
+    pub menu: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateMenu<'info> {
+    pub fn migrate_menu(&mut self) -> Result<()> {
+        let legacy = {
+            let data = self.menu.try_borrow_data()?;
+            require!(
+                data.len() == LEGACY_MENU_SPACE && data[..8] == Menu::DISCRIMINATOR,
+                SetupError::NotALegacyAccount
+            );
+            LegacyMenu::deserialize(&mut &data[8..]).map_err(|_| SetupError::NotALegacyAccount)?
+        };
+
+        let menu = Menu {
+            initialized: legacy.initialized,
+            bump: legacy.bump,
+            sections: Vec::new(),
+        };
+
+        let menu_info = self.menu.to_account_info();
+        resize_program_account(
+            &menu_info,
+            Menu::INIT_SPACE,
+            &self.restaurant_admin.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+        menu.try_serialize(&mut &mut menu_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateMenu>) -> Result<()> {
+    ctx.accounts.migrate_menu()
+}
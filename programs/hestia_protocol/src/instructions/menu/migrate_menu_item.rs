@@ -0,0 +1,169 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use crate::{
+    state::{IngredientList, MenuCategoryType, MenuItem, Restaurant},
+    errors::{BuyingError, MenuError, SetupError},
+    constants::admin_wallet as ADMIN,
+};
+
+/*
+    Migrate Menu Item Instruction
+
+    Functionality:
+    - Moves a menu item from the legacy [b"menu_item", sku] address to the restaurant-scoped
+      [b"menu_item", restaurant, sku] address, along with its IngredientList
+    - Copies sku, category, name, price, description and active status; the new item records its restaurant
+    - Closes the legacy MenuItem and IngredientList accounts, returning their rent to the protocol admin
+    - Price schedules, modifier groups and combo slots are keyed on the menu item address, they are not
+      carried over and must be set again on the migrated item
+    - The migrated item starts with no active vouchers: legacy RewardVouchers stored a numeric item_sku and can't
+      be bought or removed until restaurant_migrate_reward_voucher links them to this item, which counts them
+
+    Security checks:
+    - Only the protocol admin can migrate, legacy menu items don't record which restaurant created them
+    - The legacy menu item must be a MenuItem account owned by this program with the requested SKU
+    - The legacy IngredientList is derived from the legacy menu item
+*/
+
+// Legacy MenuItem: sku, category, name, price, description, active, bump
+const LEGACY_MENU_ITEM_SPACE: usize = 8 + 4 + MenuCategoryType::INIT_SPACE + 4 + 8 + 4 + 4 + 1;
+
+#[derive(AnchorDeserialize)]
+struct LegacyMenuItem {
+    sku: String,
+    category: MenuCategoryType,
+    name: String,
+    price: u64,
+    description: String,
+    active: bool,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MigrateMenuItemArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MigrateMenuItemArgs)]
+pub struct MigrateMenuItem<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"menu_item", args.sku.as_bytes()],
+        bump,
+    )]
+    /// CHECK: Legacy MenuItem layout, deserialized in the instruction
+    pub legacy_menu_item: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"ingredient_list", legacy_menu_item.key().as_ref()],
+        bump = legacy_ingredient_list.bump,
+    )]
+    pub legacy_ingredient_list: Account<'info, IngredientList>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = MenuItem::INIT_SPACE + legacy_menu_item.data_len().saturating_sub(LEGACY_MENU_ITEM_SPACE),
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = IngredientList::INIT_SPACE + legacy_ingredient_list.ingredients.len() * (32 + 8),
+        seeds = [b"ingredient_list", menu_item.key().as_ref()],
+        bump,
+    )]
+    pub ingredient_list: Account<'info, IngredientList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateMenuItem<'info> {
+    pub fn migrate_menu_item(&mut self, args: MigrateMenuItemArgs, menu_item_bump: u8, ingredient_list_bump: u8) -> Result<()> {
+        let legacy = {
+            let data = self.legacy_menu_item.try_borrow_data()?;
+            require!(
+                data.len() >= LEGACY_MENU_ITEM_SPACE && data[..8] == MenuItem::DISCRIMINATOR,
+                MenuError::InvalidMenuItem
+            );
+            LegacyMenuItem::deserialize(&mut &data[8..]).map_err(|_| MenuError::InvalidMenuItem)?
+        };
+        require!(legacy.sku == args.sku, MenuError::InvalidSku);
+
+        self.menu_item.set_inner(MenuItem {
+            restaurant: self.restaurant.key(),
+            sku: legacy.sku,
+            category: legacy.category,
+            name: legacy.name,
+            price: legacy.price,
+            description: legacy.description,
+            active: legacy.active,
+            price_schedule: None,
+            modifier_groups: Vec::new(),
+            combo: None,
+            active_vouchers: 0,
+            combo_references: 0,
+            bump: menu_item_bump,
+        });
+
+        self.ingredient_list.set_inner(IngredientList {
+            menu_item: self.menu_item.key(),
+            ingredients: self.legacy_ingredient_list.ingredients.clone(),
+            bump: ingredient_list_bump,
+        });
+
+        // The legacy IngredientList is closed by the `close = admin` constraint
+        self.close_legacy_menu_item()?;
+
+        emit!(MenuItemMigrated {
+            sku: self.menu_item.sku.clone(),
+            legacy_menu_item: self.legacy_menu_item.key(),
+            menu_item: self.menu_item.key(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    fn close_legacy_menu_item(&self) -> Result<()> {
+        let legacy_menu_item = self.legacy_menu_item.to_account_info();
+        let admin = self.admin.to_account_info();
+
+        let lamports = legacy_menu_item.lamports();
+        **admin.try_borrow_mut_lamports()? = admin.lamports()
+            .checked_add(lamports)
+            .ok_or(BuyingError::Overflow)?;
+        **legacy_menu_item.try_borrow_mut_lamports()? = 0;
+
+        legacy_menu_item.assign(&system_program::ID);
+        legacy_menu_item.realloc(0, false)?;
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateMenuItem>, args: MigrateMenuItemArgs) -> Result<()> {
+    // Verify that the signer is the admin of the protocol
+    require!(ctx.accounts.admin.key() == ADMIN::id(), SetupError::Unauthorized);
+
+    ctx.accounts.migrate_menu_item(args, ctx.bumps.menu_item, ctx.bumps.ingredient_list)
+}
+
+// Event emitted when a legacy menu item is moved to its restaurant-scoped address
+#[event]
+pub struct MenuItemMigrated {
+    pub sku: String,
+    pub legacy_menu_item: Pubkey,
+    pub menu_item: Pubkey,
+    pub restaurant: Pubkey,
+}
@@ -2,4 +2,34 @@ pub mod add_menu_item;
 pub use add_menu_item::*;
 
 pub mod toggle_menu_item;
-pub use toggle_menu_item::*;
\ No newline at end of file
+pub use toggle_menu_item::*;
+
+pub mod remove_menu_item;
+pub use remove_menu_item::*;
+
+pub mod set_price_schedule;
+pub use set_price_schedule::*;
+
+pub mod remove_price_schedule;
+pub use remove_price_schedule::*;
+
+pub mod add_modifier_group;
+pub use add_modifier_group::*;
+
+pub mod remove_modifier_group;
+pub use remove_modifier_group::*;
+
+pub mod set_combo;
+pub use set_combo::*;
+
+pub mod remove_combo;
+pub use remove_combo::*;
+
+pub mod migrate_menu_item;
+pub use migrate_menu_item::*;
+
+pub mod menu_sections;
+pub use menu_sections::*;
+
+pub mod migrate_menu;
+pub use migrate_menu::*;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Combo, MenuItem, Restaurant},
+    errors::SetupError,
+    utils::update_combo_references,
+};
+
+/*
+    Remove Combo Instruction
+
+    Functionality:
+    - Removes the component slots of a combo menu item, which is then sold as a plain menu item
+    - Closes the Combo account and returns the rent to the restaurant admin
+    - Releases the combo's hold on its components and substitutes (combo_references)
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The combo is derived from the menu item
+
+    Remaining accounts:
+    - Every menu item referenced by the slots, writable and in slot order (component, then substitutes)
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemoveComboArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RemoveComboArgs)]
+pub struct RemoveCombo<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        mut,
+        close = restaurant_admin,
+        seeds = [b"combo", menu_item.key().as_ref()],
+        bump = combo.bump,
+    )]
+    pub combo: Account<'info, Combo>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RemoveCombo<'info> {
+    pub fn remove_combo(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        // The account itself is closed by the `close = restaurant_admin` constraint
+        update_combo_references(remaining_accounts, &self.restaurant.key(), &Combo::referenced_items(&self.combo.slots), &[])?;
+        self.menu_item.combo = None;
+
+        emit!(ComboRemoved {
+            sku: self.menu_item.sku.clone(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveCombo<'info>>, _args: RemoveComboArgs) -> Result<()> {
+    ctx.accounts.remove_combo(ctx.remaining_accounts)
+}
+
+// Event emitted when a combo's slots are removed
+#[event]
+pub struct ComboRemoved {
+    pub sku: String,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, IngredientList, Menu, MenuItem, Restaurant},
+    errors::{MenuError, SetupError},
+    utils::resize_program_account,
+};
+
+/*
+    Remove Menu Item Instruction
+
+    Functionality:
+    - Deletes a menu item from the restaurant's menu
+    - Closes the MenuItem and its IngredientList, returning the rent to the restaurant admin
+    - Drops the item from every section of the restaurant's Menu, when one is passed
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The menu item must be deactivated first, so no new orders reference it
+    - Refuses while an active RewardVoucher references the item's SKU
+    - Refuses while another combo uses the item as a component or substitute
+    - Refuses while a price schedule, modifier group or combo slots are attached, so none are left orphaned
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemoveMenuItemArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RemoveMenuItemArgs)]
+pub struct RemoveMenuItem<'info> {
+    #[account(
+        mut,
+        close = restaurant_admin,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        mut,
+        close = restaurant_admin,
+        seeds = [b"ingredient_list", menu_item.key().as_ref()],
+        bump = ingredient_list.bump,
+    )]
+    pub ingredient_list: Account<'info, IngredientList>,
+
+    #[account(
+        mut,
+        seeds = [b"menu", restaurant.key().as_ref()],
+        bump = menu.bump,
+    )]
+    pub menu: Option<Account<'info, Menu>>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RemoveMenuItem<'info> {
+    pub fn remove_menu_item(&mut self) -> Result<()> {
+        // Both accounts are closed by their `close = restaurant_admin` constraints
+        require!(!self.menu_item.active, MenuError::MenuItemActive);
+        require!(self.menu_item.active_vouchers == 0, MenuError::MenuItemHasVouchers);
+        require!(self.menu_item.combo_references == 0, MenuError::MenuItemInCombo);
+        require!(
+            self.menu_item.price_schedule.is_none()
+                && self.menu_item.modifier_groups.is_empty()
+                && self.menu_item.combo.is_none(),
+            MenuError::MenuItemHasDependents
+        );
+
+        if let Some(menu) = self.menu.as_mut() {
+            let menu_item = self.menu_item.key();
+            for section in menu.sections.iter_mut() {
+                section.items.retain(|key| *key != menu_item);
+            }
+            menu.sections.retain(|section| !section.items.is_empty());
+
+            resize_program_account(
+                &menu.to_account_info(),
+                menu.space(),
+                &self.restaurant_admin.to_account_info(),
+                &self.system_program.to_account_info(),
+            )?;
+        }
+
+        emit!(MenuItemRemoved {
+            sku: self.menu_item.sku.clone(),
+            menu_item: self.menu_item.key(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RemoveMenuItem>, _args: RemoveMenuItemArgs) -> Result<()> {
+    ctx.accounts.remove_menu_item()
+}
+
+// Event emitted when a menu item is removed
+#[event]
+pub struct MenuItemRemoved {
+    pub sku: String,
+    pub menu_item: Pubkey,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, MenuItem, ModifierGroup, Restaurant},
+    errors::SetupError,
+};
+
+/*
+    Remove Modifier Group Instruction
+
+    Functionality:
+    - Detaches a ModifierGroup from its menu item; later order lines no longer select from it
+    - Closes the ModifierGroup account and returns the rent to the restaurant admin
+    - Selection indices of the remaining groups shift down, as they follow MenuItem.modifier_groups
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The group is derived from the menu item
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemoveModifierGroupArgs {
+    sku: String,
+    name: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RemoveModifierGroupArgs)]
+pub struct RemoveModifierGroup<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        mut,
+        close = restaurant_admin,
+        seeds = [b"modifier_group", menu_item.key().as_ref(), args.name.as_bytes()],
+        bump = modifier_group.bump,
+    )]
+    pub modifier_group: Account<'info, ModifierGroup>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RemoveModifierGroup<'info> {
+    pub fn remove_modifier_group(&mut self) -> Result<()> {
+        // The account itself is closed by the `close = restaurant_admin` constraint
+        let modifier_group = self.modifier_group.key();
+        self.menu_item.modifier_groups.retain(|key| *key != modifier_group);
+
+        emit!(ModifierGroupRemoved {
+            sku: self.menu_item.sku.clone(),
+            modifier_group,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RemoveModifierGroup>, _args: RemoveModifierGroupArgs) -> Result<()> {
+    ctx.accounts.remove_modifier_group()
+}
+
+// Event emitted when a modifier group is removed from a menu item
+#[event]
+pub struct ModifierGroupRemoved {
+    pub sku: String,
+    pub modifier_group: Pubkey,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, MenuItem, PriceSchedule, Restaurant},
+    errors::SetupError,
+};
+
+/*
+    Remove Price Schedule Instruction
+
+    Functionality:
+    - Removes the PriceSchedule of a menu item, which goes back to its static price
+    - Closes the PriceSchedule account and returns the rent to the restaurant admin
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The schedule is derived from the menu item
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemovePriceScheduleArgs {
+    sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RemovePriceScheduleArgs)]
+pub struct RemovePriceSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        mut,
+        close = restaurant_admin,
+        seeds = [b"price_schedule", menu_item.key().as_ref()],
+        bump = price_schedule.bump,
+    )]
+    pub price_schedule: Account<'info, PriceSchedule>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RemovePriceSchedule<'info> {
+    pub fn remove_price_schedule(&mut self) -> Result<()> {
+        // The account itself is closed by the `close = restaurant_admin` constraint
+        self.menu_item.price_schedule = None;
+
+        emit!(PriceScheduleRemoved {
+            sku: self.menu_item.sku.clone(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RemovePriceSchedule>, _args: RemovePriceScheduleArgs) -> Result<()> {
+    ctx.accounts.remove_price_schedule()
+}
+
+// Event emitted when a menu item's price schedule is removed
+#[event]
+pub struct PriceScheduleRemoved {
+    pub sku: String,
+    pub restaurant: Pubkey,
+}
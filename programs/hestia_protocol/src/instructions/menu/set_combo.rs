@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Combo, ComboSlot, MenuCategoryType, MenuItem, Restaurant},
+    errors::{MenuError, SetupError},
+    utils::update_combo_references,
+};
+
+/*
+    Set Combo Instruction
+
+    Functionality:
+    - Creates or replaces the component slots of a combo menu item
+    - Each slot references a component MenuItem, the units of it included per combo and optional substitutes
+    - The combo is sold at its own MenuItem.price (the bundle price); ordering it depletes every component's ingredients
+    - Links the combo to the menu item so orders always resolve its components
+    - Counts the combo in the combo_references of every component and substitute, so they can't be removed
+      while the combo uses them
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Only MenuCategoryType::Combo items can have slots
+    - Validates every slot: positive quantity, bounded substitutes, no slot referencing the combo itself
+    - Components and substitutes must be menu items of the restaurant
+
+    Remaining accounts:
+    - Every menu item referenced by the current slots (when replacing them), then every menu item referenced
+      by the new slots and not by the current ones, writable and in slot order (component, then substitutes)
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SetComboArgs {
+    sku: String,
+    slots: Vec<ComboSlot>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: SetComboArgs)]
+pub struct SetCombo<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        init_if_needed,
+        payer = restaurant_admin,
+        space = Combo::INIT_SPACE,
+        seeds = [b"combo", menu_item.key().as_ref()],
+        bump,
+    )]
+    pub combo: Account<'info, Combo>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetCombo<'info> {
+    pub fn set_combo(&mut self, slots: Vec<ComboSlot>, bump: u8, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(self.menu_item.category == MenuCategoryType::Combo, MenuError::NotACombo);
+
+        let combo_item = self.menu_item.key();
+        require!(
+            !slots.is_empty()
+                && slots.len() <= Combo::MAX_SLOTS
+                && slots.iter().all(|slot| {
+                    slot.quantity > 0
+                        && slot.substitutes.len() <= Combo::MAX_SUBSTITUTES
+                        && slot.menu_item != combo_item
+                        && !slot.substitutes.contains(&combo_item)
+                }),
+            MenuError::InvalidComboSlots
+        );
+
+        // A new Combo account has no slots yet
+        let previous = match self.combo.menu_item == combo_item {
+            true => Combo::referenced_items(&self.combo.slots),
+            false => Vec::new(),
+        };
+        update_combo_references(remaining_accounts, &self.restaurant.key(), &previous, &Combo::referenced_items(&slots))?;
+
+        self.combo.set_inner(Combo {
+            menu_item: combo_item,
+            slots,
+            bump,
+        });
+        self.menu_item.combo = Some(self.combo.key());
+
+        emit!(ComboUpdated {
+            sku: self.menu_item.sku.clone(),
+            price: self.menu_item.price,
+            slots: self.combo.slots.clone(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SetCombo<'info>>, args: SetComboArgs) -> Result<()> {
+    ctx.accounts.set_combo(args.slots, ctx.bumps.combo, ctx.remaining_accounts)
+}
+
+// Event emitted when a combo's slots are created or replaced
+#[event]
+pub struct ComboUpdated {
+    pub sku: String,
+    pub price: u64,
+    pub slots: Vec<ComboSlot>,
+    pub restaurant: Pubkey,
+}
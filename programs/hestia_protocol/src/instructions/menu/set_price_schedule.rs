@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, MenuItem, PriceAdjustment, PriceSchedule, PriceWindow, Restaurant},
+    errors::{MenuError, SetupError},
+    constants::BPS_DENOMINATOR,
+};
+
+/*
+    Set Price Schedule Instruction
+
+    Functionality:
+    - Creates or replaces the PriceSchedule of a menu item (e.g. happy hour)
+    - Each window covers a set of local weekdays and a [start, end) range of minutes since local midnight,
+      evaluated in the restaurant's UTC offset
+    - A matching window either replaces the price or takes a percentage off MenuItem.price
+    - Links the schedule to the menu item so orders always price it
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Validates every window: at least one weekday, start before end within the day, rates up to 10000 bps
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SetPriceScheduleArgs {
+    sku: String,
+    windows: Vec<PriceWindow>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: SetPriceScheduleArgs)]
+pub struct SetPriceSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    #[account(
+        init_if_needed,
+        payer = restaurant_admin,
+        space = PriceSchedule::INIT_SPACE,
+        seeds = [b"price_schedule", menu_item.key().as_ref()],
+        bump,
+    )]
+    pub price_schedule: Account<'info, PriceSchedule>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPriceSchedule<'info> {
+    pub fn set_price_schedule(&mut self, windows: Vec<PriceWindow>, bump: u8) -> Result<()> {
+        require!(windows.len() <= PriceSchedule::MAX_WINDOWS, MenuError::TooManyPriceWindows);
+
+        for window in windows.iter() {
+            let valid_adjustment = match window.adjustment {
+                PriceAdjustment::Price { .. } => true,
+                PriceAdjustment::PercentOff { bps } => bps as u64 <= BPS_DENOMINATOR,
+            };
+            require!(
+                window.days != 0
+                    && window.days < 1 << 7
+                    && window.start_minute < window.end_minute
+                    && window.end_minute <= 24 * 60
+                    && valid_adjustment,
+                MenuError::InvalidPriceWindow
+            );
+        }
+
+        self.price_schedule.set_inner(PriceSchedule {
+            menu_item: self.menu_item.key(),
+            windows,
+            bump,
+        });
+        self.menu_item.price_schedule = Some(self.price_schedule.key());
+
+        emit!(PriceScheduleUpdated {
+            sku: self.menu_item.sku.clone(),
+            windows: self.price_schedule.windows.clone(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetPriceSchedule>, args: SetPriceScheduleArgs) -> Result<()> {
+    ctx.accounts.set_price_schedule(args.windows, ctx.bumps.price_schedule)
+}
+
+// Event emitted when a menu item's price schedule is created or replaced
+#[event]
+pub struct PriceScheduleUpdated {
+    pub sku: String,
+    pub windows: Vec<PriceWindow>,
+    pub restaurant: Pubkey,
+}
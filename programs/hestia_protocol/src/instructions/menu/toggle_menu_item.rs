@@ -23,7 +23,7 @@ pub struct ToggleMenuItemArgs {
 pub struct ToggleMenuItem<'info> {
     #[account(
         mut,
-        seeds = [b"item", restaurant.key().as_ref(), args.sku.as_bytes()],
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.sku.as_bytes()],
         bump = item.bump,
     )] 
     pub item: Account<'info, MenuItem>,
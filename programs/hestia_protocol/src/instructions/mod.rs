@@ -5,7 +5,10 @@ pub mod initialization;
 pub mod inventory;
 pub mod menu;
 pub mod order;
+pub mod promotions;
 pub mod rewards;
+pub mod suppliers;
+pub mod tips;
 
 pub use admin::*;
 pub use customer::*;
@@ -14,4 +17,7 @@ pub use initialization::*;
 pub use inventory::*;
 pub use menu::*;
 pub use order::*;
-pub use rewards::*;
\ No newline at end of file
+pub use promotions::*;
+pub use rewards::*;
+pub use suppliers::*;
+pub use tips::*;
\ No newline at end of file
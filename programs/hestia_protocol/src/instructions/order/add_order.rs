@@ -4,17 +4,23 @@ use anchor_lang::{
         self,
         load_current_index_checked,
         load_instruction_at_checked
-    }
+    },
+    system_program::{transfer, Transfer},
 };
 use anchor_spl::{
     associated_token::AssociatedToken, 
-    token::{Mint, TokenAccount, Token, transfer, Transfer}
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use std::str::FromStr;
 use crate::{
-    state::{MenuItem, Customer, CustomerOrder, Restaurant, StatusType, Manager, RewardVoucher}, 
-    errors::BuyingError,
-    constants::{signing_authority, ED25519_PROGRAM_ID},
+    state::{MenuItem, ModifierSelection, ComboSubstitution, Customer, CustomerOrder, OrderLine, BillShare, StockTaken, Promotion, PromotionRedemption, Restaurant, StatusType, Manager, RewardVoucher, PaymentConfig, PaymentReceipt, StripePaymentMessage, TipPool}, 
+    errors::{BuyingError, OrderError, PromotionError, RewardError, TipError},
+    constants::{ED25519_PROGRAM_ID, ORDER_ESCROW_TIMEOUT},
+    utils::{
+        is_native_sol, load_ed25519_message, load_order_items, move_ingredient_stock, order_total, resize_program_account,
+        reward_points_for, stock_taken, transfer_tokens, bps_of, order_tax, promotion_discount, LineRequest, LocalTime,
+        OrderItem, StockMovement, NATIVE_SOL_DECIMALS,
+    },
 };
 
 use mpl_core::{
@@ -29,36 +35,102 @@ use mpl_core::{
     Functionality:
     - Creates a new CustomerOrder account
     - Initializes a Customer account if it doesn't exist
-    - Handles payment for the order (either direct token transfer or Stripe payment)
+    - Prices the order on-chain from the ordered MenuItem accounts, all amounts in currency base units
+    - Applies happy-hour PriceSchedule windows evaluated in the restaurant's UTC offset
+    - Validates the selected modifiers of each line and prices them on-chain
+    - Sells combo items at their bundle price, resolving each slot to its default component or the chosen substitute,
+      and reports the revenue allocated to every component in a ComboSold event
+    - Applies an optional promo code: percent-off, fixed-off or buy-X-get-Y, optionally scoped to a MenuCategoryType
+    - Adds sales tax (per MenuCategoryType rate) and the restaurant's service charge on top of the discounted subtotal,
+      recording subtotal, discount, tax and service charge separately on the order
+    - Accrues reward points at the restaurant's reward_points_per_token rate
+    - Handles payment for the order (native SOL, SPL Token / Token-2022 transfer, or Stripe payment)
+    - In escrow mode, holds the payment in an order-scoped escrow until the order is completed
+      (an escrow token account for tokens, the order account itself for native SOL)
+    - Transfers an optional tip alongside the payment into the restaurant's TipPool
+      (its tip vault for tokens, the TipPool account itself for native SOL)
+    - Split-bill mode: the balance due is divided into shares that are each paid by a different wallet
+      through the pay order share instruction, nothing is charged when the order is placed
     - Burns a reward voucher if used
+    - Depletes InventoryItem stock from the IngredientList of each ordered MenuItem and its selected modifier options,
+      including the IngredientList of every combo component
+    - Records the stock taken from each InventoryItem on the order, so cancelling restores exactly that
+      whatever happens to the menu afterwards (the order account grows to fit it, paid by the signer)
 
     Security checks:
     - Verifies the signer's authority
-    - Checks the validity of Stripe payments
-    - Ensures the correct reward voucher is used (if applicable)
+    - The order customer must be the signer, refunds are paid to it
+    - The order account is created here and can't already exist, so a live order (and its escrow) can't be overwritten
+    - Validates the payment mint against Restaurant.currency
+    - Checks the validity of Stripe payments: the preceding Ed25519 instruction must carry a
+      StripePaymentMessage signed by an active PaymentConfig signer for this order, restaurant, customer
+      and amount, that has not expired
+    - Consumes a PaymentReceipt PDA per order so a signed payment can never be replayed
+    - Ensures the correct reward voucher is used (if applicable) and that it belongs to the restaurant
+    - Promotions must be active, within their schedule and under their total and per-customer redemption limits
+    - Rejects menu items of other restaurants, inactive menu items and totals that don't match the current (scheduled) unit price times quantity
+    - Fails if any referenced InventoryItem is short on stock
+
+    Remaining accounts:
+    - One [menu_item, ingredient_list, inventory_item..., price_schedule?, modifier_group..., option_inventory_item...,
+      combo?, component groups...] group per order line (see utils)
 */
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct OrderLineArgs {
+    menu_item: Pubkey,
+    quantity: u16,
+    modifiers: Vec<ModifierSelection>,
+    substitutions: Vec<ComboSubstitution>,
+    note: Option<String>,
+}
+
+impl OrderLineArgs {
+    /// Space taken by the resulting OrderLine inside the CustomerOrder account
+    pub fn space(&self) -> usize {
+        OrderLine::INIT_SPACE
+            + self.modifiers.len() * 2
+            + self.substitutions.len() * 2
+            + self.note.as_ref().map_or(0, |note| 4 + note.len())
+    }
+}
+
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct CustomerOrderArgs {
     order_id: u64,
     customer: Pubkey,
     customer_name: Option<String>,
-    items: Vec<u64>,
-    total: u64,
+    lines: Vec<OrderLineArgs>,
+    total: u64, // items subtotal, before reward, tax and service charge
+    tip: u64,
     status: u8,
     created_at: i64,
     updated_at: Option<i64>,
     use_reward: bool,
+    promo_code: Option<String>,
+    escrow: bool,
+    split_shares: Vec<u64>, // share amounts in base units, empty unless the bill is split
     bump: u8
 }
 
+/// Breakdown of what the customer is charged, in currency base units
+pub struct OrderCharges {
+    pub subtotal: u64,
+    pub discount: u64,
+    pub tax: u64,
+    pub service_charge: u64,
+    pub total: u64,
+}
+
 #[derive(Accounts)]
 #[instruction(args: CustomerOrderArgs)]
 pub struct AddCustomerOrder<'info> {
     #[account(
-        init_if_needed,
+        init,
         payer = signer,
-        space = CustomerOrder::INIT_SPACE,
+        space = CustomerOrder::INIT_SPACE
+            + args.lines.iter().map(|line| line.space()).sum::<usize>()
+            + args.split_shares.len() * BillShare::INIT_SPACE,
         seeds = [b"order", restaurant.key().as_ref(), args.order_id.to_le_bytes().as_ref()],
         bump,
     )] 
@@ -76,22 +148,80 @@ pub struct AddCustomerOrder<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    #[account()]
-    pub currency: Account<'info, Mint>,
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
         associated_token::mint = currency,
         associated_token::authority = signer,
+        associated_token::token_program = token_program,
     )]
-    pub signer_ata: Account<'info, TokenAccount>,
+    pub signer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = currency,
         associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"escrow", order.key().as_ref()],
+        bump,
+        token::mint = currency,
+        token::authority = order,
+        token::token_program = token_program,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = PaymentReceipt::INIT_SPACE,
+        seeds = [b"receipt", order.key().as_ref()],
+        bump,
+    )]
+    pub receipt: Option<Account<'info, PaymentReceipt>>,
+
+    #[account(
+        seeds = [b"payment_config"],
+        bump = payment_config.bump,
+    )]
+    pub payment_config: Option<Account<'info, PaymentConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"promotion", restaurant.key().as_ref(), args.promo_code.as_ref().unwrap().as_bytes()],
+        bump = promotion.bump,
+    )]
+    pub promotion: Option<Account<'info, Promotion>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = PromotionRedemption::INIT_SPACE,
+        seeds = [b"promotion_redemption", promotion.as_ref().unwrap().key().as_ref(), signer.key().as_ref()],
+        bump,
     )]
-    pub restaurant_ata: Account<'info, TokenAccount>,
+    pub promotion_redemption: Option<Account<'info, PromotionRedemption>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump = tip_pool.bump,
+    )]
+    pub tip_pool: Option<Account<'info, TipPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_vault", tip_pool.as_ref().unwrap().key().as_ref()],
+        bump,
+    )]
+    pub tip_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)] 
     pub restaurant: Account<'info, Restaurant>,
@@ -108,6 +238,7 @@ pub struct AddCustomerOrder<'info> {
     #[account(
         seeds = [b"voucher", reward.as_ref().unwrap().key().as_ref()],
         bump,
+        constraint = voucher.restaurant == restaurant.key() @ RewardError::InvalidRestaurant,
     )] 
     pub voucher: Option<Account<'info, RewardVoucher>>,
 
@@ -115,17 +246,17 @@ pub struct AddCustomerOrder<'info> {
     pub customer_voucher: Option<Signer<'info>>,
 
     #[account(
-        seeds = [b"item", voucher.as_ref().unwrap().item_sku.to_le_bytes().as_ref()],
-        bump,
+        seeds = [b"menu_item", restaurant.key().as_ref(), voucher.as_ref().unwrap().item_sku.as_bytes()],
+        bump = menu_item.bump,
     )] 
-    pub menu_item: Account<'info, MenuItem>,
+    pub menu_item: Option<Account<'info, MenuItem>>,
 
     #[account(address = instructions::ID)]
     /// CHECK: InstructionsSysvar account
     pub instructions: UncheckedAccount<'info>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 
     #[account(address = MPL_CORE_PROGRAM_ID)]
     /// CHECK: This account will be checked by the constraint
@@ -135,13 +266,22 @@ pub struct AddCustomerOrder<'info> {
 }
 
 impl<'info> AddCustomerOrder<'info> {
-    pub fn add_order(&mut self, args: CustomerOrderArgs, balance_due: u64, customer_bump: u8, order_bump: u8) -> Result<()> {
+    pub fn add_order(&mut self, args: CustomerOrderArgs, lines: Vec<OrderLine>, charges: OrderCharges, paid: u64, customer_bump: u8, order_bump: u8) -> Result<()> {
+        let balance_due = charges.total;
+
         if args.use_reward {
             self.burn_reward_voucher()?;
         }
 
-        self.initialize_or_update_customer(&args, balance_due, customer_bump)?;
-        self.create_order(&args, balance_due, order_bump)?;
+        // Split bills accrue points and count orders for each payer as their share is paid
+        let split = !args.split_shares.is_empty();
+        let reward_points = match split {
+            true => 0,
+            false => reward_points_for(balance_due, self.restaurant.reward_points_per_token, self.currency_decimals()?)?,
+        };
+
+        self.initialize_or_update_customer(&args, !split, reward_points, customer_bump)?;
+        self.create_order(&args, lines, charges, paid, reward_points, order_bump)?;
 
         Ok(())
     }
@@ -159,33 +299,62 @@ impl<'info> AddCustomerOrder<'info> {
         Ok(())
     }
 
-    fn initialize_or_update_customer(&mut self, args: &CustomerOrderArgs, balance_due: u64, customer_bump: u8) -> Result<()> {
+    fn initialize_or_update_customer(&mut self, args: &CustomerOrderArgs, count_order: bool, reward_points: u64, customer_bump: u8) -> Result<()> {
         if !self.customer.initialized {
-            self.customer.set_inner(Customer {
-                initialized: true,
-                name: args.customer_name.clone().unwrap_or("no name".to_string()),
-                restaurant: self.restaurant.key(),
-                member_since: Clock::get()?.unix_timestamp - 20 * 60 * 60,
-                total_orders: 1,
-                reward_points: balance_due / 10u64.pow(self.currency.decimals as u32), // 1 point per 1 token
-                bump: customer_bump
-            });
+            self.customer.set_inner(Customer::new(
+                args.customer_name.clone(),
+                self.restaurant.key(),
+                Clock::get()?.unix_timestamp,
+                count_order as u64,
+                reward_points,
+                customer_bump,
+            ));
         } else {
-            self.customer.total_orders += 1;
-            self.customer.reward_points += balance_due / 10u64.pow(self.currency.decimals as u32);
+            self.customer.total_orders = self.customer.total_orders
+                .checked_add(count_order as u64)
+                .ok_or(BuyingError::Overflow)?;
+            self.customer.reward_points = self.customer.reward_points
+                .checked_add(reward_points)
+                .ok_or(BuyingError::Overflow)?;
         }
 
         Ok(())
     }
 
-    fn create_order(&mut self, args: &CustomerOrderArgs, balance_due: u64, order_bump: u8) -> Result<()> {
+    fn create_order(&mut self, args: &CustomerOrderArgs, lines: Vec<OrderLine>, charges: OrderCharges, paid: u64, reward_points: u64, order_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
         self.order.set_inner(CustomerOrder {
             order_id: args.order_id,
             customer: args.customer,
-            items: args.items.clone(),
-            total: balance_due,
+            lines,
+            subtotal: charges.subtotal,
+            discount: charges.discount,
+            promotion: self.promotion.as_ref().map(|promotion| promotion.key()),
+            tax: charges.tax,
+            service_charge: charges.service_charge,
+            total: charges.total,
+            paid,
+            refunded: 0,
+            tip: args.tip,
+            reward_points,
+            shares: args.split_shares
+                .iter()
+                .map(|amount| BillShare {
+                    amount: *amount,
+                    payer: None,
+                    refunded: 0,
+                    reward_points: 0,
+                    paid_at: None,
+                })
+                .collect(),
+            stock_taken: Vec::new(),
+            escrow_expires_at: match args.escrow {
+                true => Some(now.checked_add(ORDER_ESCROW_TIMEOUT).ok_or(BuyingError::Overflow)?),
+                false => None,
+            },
             status: StatusType::Pending,
-            created_at: Clock::get()?.unix_timestamp - 20 * 60 * 60,
+            created_at: now - 20 * 60 * 60,
             updated_at: None,
             bump: order_bump
         });
@@ -193,76 +362,309 @@ impl<'info> AddCustomerOrder<'info> {
         Ok(())
     }
 
-    pub fn pay_order(&self, balance_due: u64) -> Result<()> {
-        transfer(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: self.signer_ata.to_account_info(),
-                    to: self.restaurant_ata.to_account_info(),
-                    authority: self.signer.to_account_info(),
-                }
-            ),
-            balance_due * 10u64.pow(self.currency.decimals as u32),
-        )?;
+    /// Grows the order account to fit `entries` StockTaken records, before any payment lands in it
+    fn reserve_stock_taken(&self, entries: usize) -> Result<()> {
+        let order_info = self.order.to_account_info();
+        let new_len = order_info.data_len() + entries * StockTaken::INIT_SPACE;
 
-        Ok(())
+        resize_program_account(&order_info, new_len, &self.signer.to_account_info(), &self.system_program.to_account_info())
     }
 
-    pub fn stripe_payment(&self, current_index: usize, balance_due: u64) -> Result<()> {
-        let ixs = self.instructions.to_account_info();
+    fn currency_decimals(&self) -> Result<u8> {
+        match is_native_sol(&self.restaurant.currency) {
+            true => Ok(NATIVE_SOL_DECIMALS),
+            false => Ok(self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.decimals),
+        }
+    }
+
+    /// Transfers the balance due (in base units) to the restaurant (or the order escrow) and returns the amount paid
+    pub fn pay_order(&self, balance_due: u64, escrow: bool) -> Result<u64> {
+        let amount = balance_due;
+
+        if is_native_sol(&self.restaurant.currency) {
+            // Escrowed SOL is held by the order account itself
+            let to = match escrow {
+                true => self.order.to_account_info(),
+                false => self.restaurant.to_account_info(),
+            };
+
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.signer.to_account_info(),
+                        to,
+                    }
+                ),
+                amount,
+            )?;
+
+            return Ok(amount);
+        }
+
+        let currency = self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+        let token_program = self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+        let signer_ata = self.signer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+        let to = match escrow {
+            true => self.escrow.as_ref().ok_or(OrderError::MissingEscrow)?.to_account_info(),
+            false => self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+        };
+
+        transfer_tokens(
+            token_program,
+            currency,
+            signer_ata.to_account_info(),
+            to,
+            self.signer.to_account_info(),
+            &[],
+            amount,
+        )?;
 
-        if let Ok(signature_ix) = load_instruction_at_checked(current_index - 1, &ixs) {
-            if Pubkey::from_str(ED25519_PROGRAM_ID).unwrap() == signature_ix.program_id {
-                require!(
-                    signing_authority::ID.to_bytes().eq(&signature_ix.data[16..48]),
-                    BuyingError::SignatureAuthorityMismatch
-                );
+        Ok(amount)
+    }
+
+    /// Validates the promotion and records its redemption, returning the discount it grants
+    pub fn redeem_promotion(&mut self, order_items: &[OrderItem], redemption_bump: Option<u8>) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let customer = self.signer.key();
+        let promotion = self.promotion.as_mut().ok_or(PromotionError::MissingPromotion)?;
+        let redemption = self.promotion_redemption.as_mut().ok_or(PromotionError::MissingPromotion)?;
+
+        require!(promotion.active, PromotionError::PromotionInactive);
+        require!(now >= promotion.starts_at, PromotionError::PromotionNotStarted);
+        require!(now < promotion.ends_at, PromotionError::PromotionExpired);
+        require!(
+            promotion.max_redemptions.map_or(true, |max| promotion.redemptions < max),
+            PromotionError::MaxRedemptionsReached
+        );
+
+        if redemption.promotion == Pubkey::default() {
+            redemption.set_inner(PromotionRedemption {
+                promotion: promotion.key(),
+                customer,
+                count: 0,
+                bump: redemption_bump.ok_or(PromotionError::MissingPromotion)?,
+            });
+        }
+        require!(
+            promotion.per_customer_limit.map_or(true, |limit| redemption.count < limit),
+            PromotionError::CustomerLimitReached
+        );
 
-                let mut message_data: [u8; 4] = [0u8; 4];
-                message_data.copy_from_slice(&signature_ix.data[112..116]);
-                let amount = u32::from_le_bytes(message_data);
+        let discount = promotion_discount(order_items, promotion)?;
+        require!(discount > 0, PromotionError::PromotionNotApplicable);
 
-                msg!("The message from Signature instruction is: {}", amount);
+        promotion.redemptions = promotion.redemptions.checked_add(1).ok_or(BuyingError::Overflow)?;
+        redemption.count = redemption.count.checked_add(1).ok_or(BuyingError::Overflow)?;
 
-                let amount_paid = amount as u64;
+        Ok(discount)
+    }
 
-                require!(
-                    amount_paid <= balance_due * 10u64.pow(self.currency.decimals as u32),
-                    BuyingError::PriceMismatch
-                );
-            } else {
-                return Err(BuyingError::InvalidInstruction.into());
-            }
+    /// Transfers the tip (in base units) into the restaurant's tip pool
+    pub fn pay_tip(&mut self, tip: u64) -> Result<()> {
+        let tip_pool = self.tip_pool.as_ref().ok_or(TipError::MissingTipPool)?;
+
+        if is_native_sol(&self.restaurant.currency) {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.signer.to_account_info(),
+                        to: tip_pool.to_account_info(),
+                    }
+                ),
+                tip,
+            )?;
         } else {
-            return Err(BuyingError::InvalidInstruction.into());
+            transfer_tokens(
+                self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.signer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+                self.tip_vault.as_ref().ok_or(TipError::MissingTipPool)?.to_account_info(),
+                self.signer.to_account_info(),
+                &[],
+                tip,
+            )?;
         }
 
+        let tip_pool = self.tip_pool.as_mut().ok_or(TipError::MissingTipPool)?;
+        tip_pool.balance = tip_pool.balance.checked_add(tip).ok_or(BuyingError::Overflow)?;
+        tip_pool.total_collected = tip_pool.total_collected.checked_add(tip).ok_or(BuyingError::Overflow)?;
+
+        Ok(())
+    }
+
+    pub fn stripe_payment(&mut self, current_index: usize, args: &CustomerOrderArgs, balance_due: u64, receipt_bump: Option<u8>) -> Result<()> {
+        let ixs = self.instructions.to_account_info();
+
+        let signature_ix = load_instruction_at_checked(current_index - 1, &ixs)
+            .map_err(|_| BuyingError::InvalidInstruction)?;
+        require_keys_eq!(
+            signature_ix.program_id,
+            Pubkey::from_str(ED25519_PROGRAM_ID).unwrap(),
+            BuyingError::InvalidInstruction
+        );
+
+        let (signer, message) = load_ed25519_message(&signature_ix.data)?;
+        let payment_config = self.payment_config.as_ref().ok_or(BuyingError::SignatureAuthorityMismatch)?;
+        require!(payment_config.is_active_signer(&signer), BuyingError::SignatureAuthorityMismatch);
+
+        let payment = StripePaymentMessage::try_from_slice(&message)
+            .map_err(|_| BuyingError::InvalidInstruction)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(payment.expires_at >= now, BuyingError::PaymentMessageExpired);
+        require!(
+            payment.order_id == args.order_id
+                && payment.restaurant == self.restaurant.key()
+                && payment.customer == args.customer,
+            BuyingError::PaymentMessageMismatch
+        );
+        require!(payment.amount == balance_due, BuyingError::PriceMismatch);
+
+        // Consuming the receipt makes the signed payment single use
+        let receipt = self.receipt.as_mut().ok_or(BuyingError::MissingPaymentReceipt)?;
+        receipt.set_inner(PaymentReceipt {
+            order: self.order.key(),
+            nonce: payment.nonce,
+            amount: payment.amount,
+            consumed_at: now,
+            bump: receipt_bump.ok_or(BuyingError::MissingPaymentReceipt)?,
+        });
+
         Ok(())
     }
 }
 
-pub fn handler(ctx: Context<AddCustomerOrder>, args: CustomerOrderArgs) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AddCustomerOrder<'info>>, args: CustomerOrderArgs) -> Result<()> {
     let current_index = load_current_index_checked(&ctx.accounts.instructions.to_account_info())? as usize;
-
-    let mut balance_due = args.total;
-    if args.use_reward {
-        balance_due = balance_due.saturating_sub(ctx.accounts.menu_item.price);
+    require_keys_eq!(args.customer, ctx.accounts.signer.key(), OrderError::CustomerMismatch);
+
+    let requested_lines: Vec<LineRequest> = args.lines
+        .iter()
+        .map(|line| LineRequest {
+            menu_item: line.menu_item,
+            quantity: line.quantity as u64,
+            modifiers: line.modifiers.clone(),
+            substitutions: line.substitutions.clone(),
+        })
+        .collect();
+    let local_time = LocalTime::at(Clock::get()?.unix_timestamp, ctx.accounts.restaurant.utc_offset_minutes);
+    let order_items = load_order_items(ctx.remaining_accounts, &ctx.accounts.restaurant.key(), &requested_lines, &local_time)?;
+
+    // Never trust the client total, it must match the menu prices
+    require!(!order_items.is_empty(), OrderError::EmptyOrder);
+    require!(args.total == order_total(&order_items)?, OrderError::TotalMismatch);
+
+    // Escrowed SOL is held by the order account, so it has to be resized before the payment
+    let stock = stock_taken(&order_items)?;
+    ctx.accounts.reserve_stock_taken(stock.len())?;
+
+    let promotion_discount = match args.promo_code {
+        Some(_) => ctx.accounts.redeem_promotion(&order_items, ctx.bumps.promotion_redemption)?,
+        None => 0,
+    };
+
+    // Discounted amounts are untaxed: a redeemed reward item and category scoped promotions come
+    // off their category, other promotions are spread across the order
+    let restaurant = &ctx.accounts.restaurant;
+    let reward_item = match args.use_reward {
+        true => Some(ctx.accounts.menu_item.as_ref().ok_or(BuyingError::InvalidInstruction)?),
+        false => None,
+    };
+    let promotion_category = ctx.accounts.promotion.as_ref().and_then(|promotion| promotion.category.as_ref());
+    let reward_discount = reward_item.map_or(0, |item| item.price);
+    let mut exempt = Vec::with_capacity(2);
+    if let Some(item) = reward_item {
+        exempt.push((&item.category, reward_discount));
+    }
+    if let Some(category) = promotion_category {
+        exempt.push((category, promotion_discount));
     }
+    let prorated_discount = match promotion_category {
+        Some(_) => 0,
+        None => promotion_discount,
+    };
+
+    let subtotal = args.total;
+    let discount = reward_discount.saturating_add(promotion_discount).min(subtotal);
+    let tax = order_tax(&order_items, restaurant, &exempt, prorated_discount)?;
+    let service_charge = bps_of(subtotal - discount, restaurant.service_charge_bps)?;
+    let balance_due = (subtotal - discount)
+        .checked_add(tax)
+        .and_then(|amount| amount.checked_add(service_charge))
+        .ok_or(BuyingError::Overflow)?;
+    let charges = OrderCharges { subtotal, discount, tax, service_charge, total: balance_due };
+
+    if !args.split_shares.is_empty() {
+        require!(
+            (2..=CustomerOrder::MAX_SHARES).contains(&args.split_shares.len())
+                && args.split_shares.iter().all(|amount| *amount > 0)
+                && args.split_shares.iter().try_fold(0u64, |sum, amount| sum.checked_add(*amount)) == Some(balance_due),
+            OrderError::InvalidSplit
+        );
+    }
+
+    // Stripe payments settle off-chain, so nothing is held on-chain to refund
+    let paid = match current_index {
+        0 if !args.split_shares.is_empty() => 0,
+        0 => ctx.accounts.pay_order(balance_due, args.escrow)?,
+        _ => {
+            require!(!args.escrow, OrderError::EscrowRequiresTokenPayment);
+            require!(args.tip == 0, TipError::TipRequiresOnChainPayment);
+            require!(args.split_shares.is_empty(), OrderError::SplitRequiresOnChainPayment);
+            ctx.accounts.stripe_payment(current_index, &args, balance_due, ctx.bumps.receipt)?;
+            0
+        }
+    };
 
-    match current_index {
-        0 => ctx.accounts.pay_order(balance_due)?,
-        _ => ctx.accounts.stripe_payment(current_index, balance_due)?
+    if args.tip > 0 {
+        ctx.accounts.pay_tip(args.tip)?;
     }
-    
-    ctx.accounts.add_order(args.clone(), balance_due, ctx.bumps.customer, ctx.bumps.order)?;
+
+    move_ingredient_stock(&stock, StockMovement::Deplete, &ctx.accounts.restaurant.key())?;
+
+    // Unit prices always come from the MenuItem, never from the client
+    let lines: Vec<OrderLine> = args.lines
+        .iter()
+        .zip(order_items.iter())
+        .map(|(line, item)| OrderLine {
+            menu_item: line.menu_item,
+            quantity: line.quantity,
+            unit_price: item.unit_price,
+            modifiers: line.modifiers.clone(),
+            substitutions: line.substitutions.clone(),
+            note: line.note.clone(),
+        })
+        .collect();
+
+    ctx.accounts.add_order(args.clone(), lines.clone(), charges, paid, ctx.bumps.customer, ctx.bumps.order)?;
+    ctx.accounts.order.stock_taken = stock
+        .iter()
+        .map(|(inventory_info, quantity)| StockTaken { inventory_item: inventory_info.key(), quantity: *quantity })
+        .collect();
 
     // Emit the new order event
+    for item in order_items.iter().filter(|item| !item.components.is_empty()) {
+        emit!(ComboSold {
+            order_id: args.order_id,
+            menu_item: item.menu_item.key(),
+            quantity: item.quantity,
+            revenue: item.unit_price.checked_mul(item.quantity).ok_or(BuyingError::Overflow)?,
+            components: combo_revenue(item)?,
+            restaurant: ctx.accounts.restaurant.key(),
+        });
+    }
+
     emit!(NewOrderCreated {
         order_id: args.order_id,
         customer: args.customer,
-        items: args.items.clone(),
+        lines,
+        subtotal,
+        discount,
+        tax,
+        service_charge,
         total: balance_due,
+        tip: args.tip,
         status: StatusType::Pending,
         created_at: args.created_at,
         restaurant: ctx.accounts.restaurant.key(),
@@ -275,9 +677,123 @@ pub fn handler(ctx: Context<AddCustomerOrder>, args: CustomerOrderArgs) -> Resul
 pub struct NewOrderCreated {
     pub order_id: u64,
     pub customer: Pubkey,
-    pub items: Vec<u64>,
+    pub lines: Vec<OrderLine>,
+    pub subtotal: u64,
+    pub discount: u64,
+    pub tax: u64,
+    pub service_charge: u64,
     pub total: u64,
+    pub tip: u64,
     pub status: StatusType,
     pub created_at: i64,
     pub restaurant: Pubkey,
-}
\ No newline at end of file
+}
+
+/// Splits the gross revenue of a combo line across its components, pro rata to their standalone
+/// MenuItem prices; the last component takes the rounding remainder
+fn combo_revenue(item: &OrderItem) -> Result<Vec<ComboComponentRevenue>> {
+    let revenue = item.unit_price.checked_mul(item.quantity).ok_or(BuyingError::Overflow)?;
+
+    // Free components (e.g. all priced at zero) share the revenue by quantity instead
+    let priced = item.components.iter().any(|component| component.menu_item.price > 0);
+    let weights: Vec<u128> = item.components
+        .iter()
+        .map(|component| match priced {
+            true => (component.menu_item.price as u128).checked_mul(component.quantity as u128),
+            false => Some(component.quantity as u128),
+        })
+        .collect::<Option<Vec<u128>>>()
+        .ok_or(BuyingError::Overflow)?;
+    let total_weight = weights
+        .iter()
+        .try_fold(0u128, |total, weight| total.checked_add(*weight))
+        .ok_or(BuyingError::Overflow)?
+        .max(1);
+
+    let mut allocated: u64 = 0;
+    let mut components = Vec::with_capacity(item.components.len());
+    for (index, (component, weight)) in item.components.iter().zip(weights.iter()).enumerate() {
+        let share = match index == item.components.len() - 1 {
+            true => revenue.checked_sub(allocated).ok_or(BuyingError::Underflow)?,
+            false => ((revenue as u128).checked_mul(*weight).ok_or(BuyingError::Overflow)? / total_weight) as u64,
+        };
+        allocated = allocated.checked_add(share).ok_or(BuyingError::Overflow)?;
+
+        components.push(ComboComponentRevenue {
+            menu_item: component.menu_item.key(),
+            quantity: component.quantity.checked_mul(item.quantity).ok_or(BuyingError::Overflow)?,
+            revenue: share,
+        });
+    }
+
+    Ok(components)
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct ComboComponentRevenue {
+    pub menu_item: Pubkey,
+    pub quantity: u64, // units of the component sold
+    pub revenue: u64, // share of the combo's gross revenue, in currency base units
+}
+
+// Event emitted for every combo line of a new order
+#[event]
+pub struct ComboSold {
+    pub order_id: u64,
+    pub menu_item: Pubkey,
+    pub quantity: u64,
+    pub revenue: u64, // bundle unit price times quantity, before discounts, tax and service charge
+    pub components: Vec<ComboComponentRevenue>,
+    pub restaurant: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state::MenuCategoryType, utils::{tests::{menu_item_account, order_item}, ComboComponent}};
+
+    fn combo(unit_price: u64, quantity: u64, components: &[(u64, u64)]) -> OrderItem<'static> {
+        let mut item = order_item(MenuCategoryType::Combo, unit_price, quantity);
+        item.components = components
+            .iter()
+            .map(|(price, quantity)| ComboComponent {
+                menu_item: menu_item_account(MenuCategoryType::Entree, *price),
+                quantity: *quantity,
+            })
+            .collect();
+        item
+    }
+
+    #[test]
+    fn combo_revenue_is_split_by_standalone_price() {
+        // 2 combos of 1_000: a 900 entree, a 300 side and two 150 drinks per combo
+        let components = combo_revenue(&combo(1_000, 2, &[(900, 1), (300, 1), (150, 2)])).unwrap();
+
+        let revenue: Vec<u64> = components.iter().map(|component| component.revenue).collect();
+        let quantity: Vec<u64> = components.iter().map(|component| component.quantity).collect();
+        assert_eq!(revenue, vec![1_200, 400, 400]);
+        assert_eq!(quantity, vec![2, 2, 4]);
+    }
+
+    #[test]
+    fn combo_revenue_gives_the_rounding_remainder_to_the_last_component() {
+        let components = combo_revenue(&combo(100, 1, &[(1, 1), (1, 1), (1, 1)])).unwrap();
+
+        let revenue: Vec<u64> = components.iter().map(|component| component.revenue).collect();
+        assert_eq!(revenue, vec![33, 33, 34]);
+    }
+
+    #[test]
+    fn combo_revenue_splits_free_components_by_quantity() {
+        let components = combo_revenue(&combo(600, 1, &[(0, 1), (0, 2)])).unwrap();
+
+        let revenue: Vec<u64> = components.iter().map(|component| component.revenue).collect();
+        assert_eq!(revenue, vec![200, 400]);
+    }
+
+    #[test]
+    fn combo_revenue_rejects_overflowing_lines() {
+        assert!(combo_revenue(&combo(u64::MAX, 2, &[(1, 1)])).is_err());
+        assert!(combo_revenue(&combo(1, 2, &[(1, u64::MAX)])).is_err());
+    }
+}
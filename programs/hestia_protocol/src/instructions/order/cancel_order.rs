@@ -1,21 +1,44 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
-    state::{Customer, CustomerOrder, Restaurant, AdminProfile, StatusType},
-    errors::OrderError
+    state::{Customer, CustomerOrder, Restaurant, AdminProfile, Employee, StatusType, TipPool, Promotion, PromotionRedemption},
+    errors::{BuyingError, InventoryError, OrderError, PromotionError, TipError},
+    utils::{
+        check_payout_destination, close_empty_token_account, is_native_sol, move_ingredient_stock, transfer_program_lamports,
+        transfer_tokens, StockMovement,
+    },
+    instructions::{OrderUpdated, OrderRefunded},
 };
 
 /*
     Cancel Customer Order Instruction
 
     Functionality:
-    - Allows either the customer or the restaurant admin to cancel an order
-    - Closes the CustomerOrder account and returns the rent to the signer
+    - Allows the customer, the restaurant admin or an employee with the required role to cancel an order
     - Updates the order status to Cancelled
-    - Decrements the customer's total_orders count
+    - Decrements the customer's total_orders count and removes the reward points earned by the order
+    - Restores exactly the InventoryItem stock recorded as taken when the order was placed, so later menu changes
+      (removed items, modifier groups, combo slots, recipes) don't matter; removed inventory items are skipped
+    - Refunds whatever is left of the on-chain payment to the customer, from the order escrow
+      if the payment is still held there, otherwise from the restaurant's ATA
+    - Closes the emptied escrow token account, returning its rent to the customer
+    - Split-bill orders refund every paid share to its payer and remove that payer's order count and reward points
+    - Returns the order's tip from what is left in the TipPool; tips already distributed to employees are not
+      clawed back and never block the payment refund, the shortfall is emitted in TipRefundShortfall
+    - Gives back the promotion redemption used by the order, both to the promotion and to the customer's limit
 
     Security checks:
-    - Ensures the signer is either the customer or the restaurant admin
+    - Ensures the signer is the customer, the restaurant admin or an employee of the restaurant
+    - Customers can only cancel Pending orders, or any order not yet Completed once its escrow times out
+    - The restaurant admin can cancel any order not yet Completed
+    - Employees need the role the status transition requires: TeamLeader for Pending or Accepted orders,
+      Manager for Preparing or Ready orders
     - Verifies that the order belongs to the correct customer and restaurant
+    - Records the refunded amount on the order so it can never be refunded twice
+
+    Remaining accounts:
+    - One writable inventory_item per CustomerOrder.stock_taken entry, in the same order
+    - For split-bill orders, followed by one [payer customer, payer destination] pair per paid share, in share order
 */
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -30,14 +53,13 @@ pub struct CancelCustomerOrder<'info> {
         mut,
         seeds = [b"order", restaurant.key().as_ref(), args.order_id.to_le_bytes().as_ref()],
         bump = order.bump,
-        constraint = order.customer == customer.key() @ OrderError::InvalidCustomer,
         constraint = order.status != StatusType::Cancelled @ OrderError::AlreadyCancelled,
     )] 
     pub order: Account<'info, CustomerOrder>,
 
     #[account(
         mut,
-        seeds = [b"customer", restaurant.key().as_ref(), customer.key().as_ref()],
+        seeds = [b"customer", restaurant.key().as_ref(), order.customer.as_ref()],
         bump = customer.bump,
     )] 
     pub customer: Account<'info, Customer>,
@@ -54,56 +76,377 @@ pub struct CancelCustomerOrder<'info> {
     )]
     pub admin_profile: Account<'info, AdminProfile>,
 
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = currency,
+        token::token_program = token_program,
+        constraint = customer_ata.owner == order.customer @ OrderError::InvalidCustomer,
+    )]
+    pub customer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = order.customer @ OrderError::InvalidCustomer,
+    )]
+    pub customer_wallet: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", order.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = order.promotion == Some(promotion.key()) @ PromotionError::MissingPromotion,
+    )]
+    pub promotion: Option<Account<'info, Promotion>>,
+
+    #[account(
+        mut,
+        seeds = [b"promotion_redemption", promotion.as_ref().unwrap().key().as_ref(), order.customer.as_ref()],
+        bump = promotion_redemption.bump,
+    )]
+    pub promotion_redemption: Option<Account<'info, PromotionRedemption>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump = tip_pool.bump,
+    )]
+    pub tip_pool: Option<Account<'info, TipPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_vault", tip_pool.as_ref().unwrap().key().as_ref()],
+        bump,
+    )]
+    pub tip_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CancelCustomerOrder<'info> {
-    pub fn cancel_order(&mut self) -> Result<()> {
-        // Ensure the signer is either the customer or the restaurant admin
+    pub fn cancel_order(&mut self, payer_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        // Ensure the order can still be cancelled
+        let required_role = self.order.status
+            .transition_role(&StatusType::Cancelled)
+            .ok_or(OrderError::InvalidStatusTransition)?;
+
+        // Ensure the signer is the customer, the restaurant admin or an employee with the required role
+        let is_owner = self.signer.key() == self.restaurant.owner;
+        let has_role = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type >= required_role);
         require!(
-            self.signer.key() == self.customer.key() || self.signer.key() == self.restaurant.owner,
+            is_owner || has_role || self.signer.key() == self.order.customer,
             OrderError::Unauthorized
         );
+        if !is_owner && !has_role {
+            let now = Clock::get()?.unix_timestamp;
+            let escrow_expired = self.order.escrow_expires_at.is_some_and(|expires_at| now >= expires_at);
+            require!(
+                self.order.status == StatusType::Pending || escrow_expired,
+                OrderError::InvalidStatusTransition
+            );
+        }
+
+        // Refund whatever has not been refunded yet
+        let escrowed = self.order.escrow_expires_at.is_some();
+        match self.order.shares.is_empty() {
+            true => self.refund_remaining()?,
+            false => self.refund_shares(payer_accounts)?,
+        }
+        if escrowed && !is_native_sol(&self.restaurant.currency) {
+            self.close_escrow()?;
+        }
+        if self.order.tip > 0 {
+            self.refund_tip()?;
+        }
+        if self.order.promotion.is_some() {
+            self.release_promotion()?;
+        }
 
         // Update order status
         self.order.status = StatusType::Cancelled;
         self.order.updated_at = Some(Clock::get()?.unix_timestamp);
 
-        // Decrement customer's total_orders
-        self.customer.total_orders = self.customer.total_orders.saturating_sub(1);
-        self.customer.reward_points = self.customer.reward_points.saturating_sub(self.order.total / 100);
+        // Decrement customer's total_orders, split bills were counted per payer
+        if self.order.shares.is_empty() {
+            self.customer.total_orders = self.customer.total_orders.saturating_sub(1);
+            self.customer.reward_points = self.customer.reward_points.saturating_sub(self.order.reward_points);
+        }
 
         let order = &self.order;
 
-        // Emit the new order event
+        // Emit the order updated event
         emit!(OrderUpdated {
             order_id: order.order_id,
             customer: order.customer,
-            items: order.items.clone(),
+            lines: order.lines.clone(),
             total: order.total,
-            status: StatusType::Pending,
+            status: order.status.clone(),
             created_at: order.created_at,
             updated_at: Clock::get()?.unix_timestamp,
             restaurant: self.restaurant.key(),
         });
 
-        // The actual closing of the account is handled by the `close = signer` constraint
         Ok(())
     }
+
+    fn refund_remaining(&mut self) -> Result<()> {
+        let amount = self.order.paid.saturating_sub(self.order.refunded);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let destination = self.customer_destination()?;
+        match self.order.escrow_expires_at {
+            Some(_) => self.refund_from_escrow(destination, amount)?,
+            None => self.refund_from_restaurant(destination, amount)?,
+        }
+
+        self.order.refunded = self.order.paid;
+        self.order.escrow_expires_at = None;
+
+        emit!(OrderRefunded {
+            order_id: self.order.order_id,
+            customer: self.order.customer,
+            amount,
+            total_refunded: self.order.refunded,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    fn refund_shares(&mut self, payer_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let paid_shares: Vec<usize> = (0..self.order.shares.len())
+            .filter(|index| self.order.shares[*index].payer.is_some())
+            .collect();
+        require!(payer_accounts.len() == paid_shares.len() * 2, OrderError::InvalidPayerAccounts);
+
+        let restaurant_key = self.restaurant.key();
+        for (index, pair) in paid_shares.into_iter().zip(payer_accounts.chunks(2)) {
+            let share = self.order.shares[index].clone();
+            let payer = share.payer.ok_or(OrderError::InvalidShare)?;
+
+            let customer_info = &pair[0];
+            let mut customer = Account::<Customer>::try_from(customer_info)?;
+            let expected_customer = Pubkey::create_program_address(
+                &[b"customer", restaurant_key.as_ref(), payer.as_ref(), &[customer.bump]],
+                &crate::ID,
+            ).map_err(|_| OrderError::InvalidPayerAccounts)?;
+            require_keys_eq!(customer_info.key(), expected_customer, OrderError::InvalidPayerAccounts);
+
+            let destination = &pair[1];
+            check_payout_destination(&self.restaurant.currency, destination, &payer)?;
+
+            let amount = share.amount.saturating_sub(share.refunded);
+            if amount > 0 {
+                match self.order.escrow_expires_at {
+                    Some(_) => {
+                        // Previous shares already drew from the escrow
+                        if let Some(escrow) = self.escrow.as_mut() {
+                            escrow.reload()?;
+                        }
+                        self.refund_from_escrow(destination.clone(), amount)?
+                    },
+                    None => self.refund_from_restaurant(destination.clone(), amount)?,
+                }
+            }
+
+            // The order creator's Customer is also serialized by this instruction, update it in place
+            if customer_info.key() == self.customer.key() {
+                self.customer.total_orders = self.customer.total_orders.saturating_sub(1);
+                self.customer.reward_points = self.customer.reward_points.saturating_sub(share.reward_points);
+            } else {
+                customer.total_orders = customer.total_orders.saturating_sub(1);
+                customer.reward_points = customer.reward_points.saturating_sub(share.reward_points);
+                customer.exit(&crate::ID)?;
+            }
+
+            self.order.shares[index].refunded = share.amount;
+            self.order.refunded = self.order.refunded.saturating_add(amount);
+
+            emit!(OrderRefunded {
+                order_id: self.order.order_id,
+                customer: payer,
+                amount,
+                total_refunded: self.order.refunded,
+                restaurant: restaurant_key,
+            });
+        }
+
+        self.order.refunded = self.order.paid;
+        self.order.escrow_expires_at = None;
+
+        Ok(())
+    }
+
+    fn release_promotion(&mut self) -> Result<()> {
+        let promotion = self.promotion.as_mut().ok_or(PromotionError::MissingPromotion)?;
+        let redemption = self.promotion_redemption.as_mut().ok_or(PromotionError::MissingPromotion)?;
+
+        promotion.redemptions = promotion.redemptions.saturating_sub(1);
+        redemption.count = redemption.count.saturating_sub(1);
+
+        Ok(())
+    }
+
+    fn customer_destination(&self) -> Result<AccountInfo<'info>> {
+        match is_native_sol(&self.restaurant.currency) {
+            true => Ok(self.customer_wallet.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info()),
+            false => Ok(self.customer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info()),
+        }
+    }
+
+    fn refund_tip(&mut self) -> Result<()> {
+        let tip_pool = self.tip_pool.as_ref().ok_or(TipError::MissingTipPool)?;
+        let tip = self.order.tip.min(tip_pool.balance);
+        if tip < self.order.tip {
+            emit!(TipRefundShortfall {
+                order_id: self.order.order_id,
+                tip: self.order.tip,
+                refunded: tip,
+                shortfall: self.order.tip - tip,
+                restaurant: self.restaurant.key(),
+            });
+        }
+        if tip == 0 {
+            return Ok(());
+        }
+
+        // Native SOL tips are held by the TipPool account itself
+        if is_native_sol(&self.restaurant.currency) {
+            let customer_wallet = self.customer_wallet.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+            transfer_program_lamports(&tip_pool.to_account_info(), &customer_wallet.to_account_info(), tip)?;
+        } else {
+            let restaurant_key = self.restaurant.key();
+            let signer_seeds: &[&[u8]; 3] = &[b"tip_pool", restaurant_key.as_ref(), &[tip_pool.bump]];
+
+            transfer_tokens(
+                self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.tip_vault.as_ref().ok_or(TipError::MissingTipPool)?.to_account_info(),
+                self.customer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+                tip_pool.to_account_info(),
+                &[signer_seeds],
+                tip,
+            )?;
+        }
+
+        let tip_pool = self.tip_pool.as_mut().ok_or(TipError::MissingTipPool)?;
+        tip_pool.balance -= tip;
+        tip_pool.total_collected = tip_pool.total_collected.saturating_sub(tip);
+
+        Ok(())
+    }
+
+    fn refund_from_escrow(&self, destination: AccountInfo<'info>, amount: u64) -> Result<()> {
+        // Escrowed SOL is held by the order account itself
+        if is_native_sol(&self.restaurant.currency) {
+            return transfer_program_lamports(&self.order.to_account_info(), &destination, amount);
+        }
+
+        let escrow = self.escrow.as_ref().ok_or(OrderError::MissingEscrow)?;
+
+        let restaurant_key = self.restaurant.key();
+        let order_id = self.order.order_id.to_le_bytes();
+        let signer_seeds: &[&[u8]; 4] = &[b"order", restaurant_key.as_ref(), order_id.as_ref(), &[self.order.bump]];
+
+        // Transfer-fee mints leave less in escrow than was paid
+        transfer_tokens(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            escrow.to_account_info(),
+            destination,
+            self.order.to_account_info(),
+            &[signer_seeds],
+            amount.min(escrow.amount),
+        )
+    }
+
+    fn close_escrow(&mut self) -> Result<()> {
+        let restaurant_key = self.restaurant.key();
+        let order_id = self.order.order_id.to_le_bytes();
+        let signer_seeds: &[&[u8]; 4] = &[b"order", restaurant_key.as_ref(), order_id.as_ref(), &[self.order.bump]];
+
+        close_empty_token_account(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.escrow.as_mut().ok_or(OrderError::MissingEscrow)?,
+            self.customer_wallet.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+            self.order.to_account_info(),
+            &[signer_seeds],
+        )?;
+
+        Ok(())
+    }
+
+    fn refund_from_restaurant(&self, destination: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if is_native_sol(&self.restaurant.currency) {
+            return transfer_program_lamports(&self.restaurant.to_account_info(), &destination, amount);
+        }
+
+        let owner = self.restaurant.owner;
+        let signer_seeds: &[&[u8]; 3] = &[b"restaurant", owner.as_ref(), &[self.restaurant.bump]];
+
+        transfer_tokens(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+            destination,
+            self.restaurant.to_account_info(),
+            &[signer_seeds],
+            amount,
+        )
+    }
 }
 
-pub fn handler(ctx: Context<CancelCustomerOrder>, _args: CancelOrderArgs) -> Result<()> {
-    ctx.accounts.cancel_order()
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelCustomerOrder<'info>>, _args: CancelOrderArgs) -> Result<()> {
+    let stock_taken = ctx.accounts.order.stock_taken.clone();
+    require!(ctx.remaining_accounts.len() >= stock_taken.len(), InventoryError::MissingIngredientAccounts);
+    let (inventory_accounts, payer_accounts) = ctx.remaining_accounts.split_at(stock_taken.len());
+
+    let mut stock = Vec::with_capacity(stock_taken.len());
+    for (taken, inventory_info) in stock_taken.iter().zip(inventory_accounts.iter()) {
+        require_keys_eq!(inventory_info.key(), taken.inventory_item, InventoryError::InventoryItemMismatch);
+        // A removed InventoryItem has nothing left to restore the stock to
+        if !inventory_info.data_is_empty() {
+            stock.push((inventory_info, taken.quantity));
+        }
+    }
+
+    ctx.accounts.cancel_order(payer_accounts)?;
+
+    move_ingredient_stock(&stock, StockMovement::Restore, &ctx.accounts.restaurant.key())
 }
 
+// Event emitted when a cancelled order's tip was already distributed and can only be partially returned
 #[event]
-pub struct OrderUpdated {
+pub struct TipRefundShortfall {
     pub order_id: u64,
-    pub customer: Pubkey,
-    pub items: Vec<u64>,
-    pub total: u64,
-    pub status: StatusType,
-    pub created_at: i64,
-    pub updated_at: i64,
+    pub tip: u64,
+    pub refunded: u64,
+    pub shortfall: u64,
     pub restaurant: Pubkey,
-}
\ No newline at end of file
+}
@@ -5,4 +5,10 @@ pub mod cancel_order;
 pub use cancel_order::*;
 
 pub mod update_order;
-pub use update_order::*;
\ No newline at end of file
+pub use update_order::*;
+
+pub mod refund_order;
+pub use refund_order::*;
+
+pub mod pay_order_share;
+pub use pay_order_share::*;
\ No newline at end of file
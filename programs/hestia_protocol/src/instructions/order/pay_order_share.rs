@@ -0,0 +1,216 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    state::{Customer, CustomerOrder, Restaurant, StatusType},
+    errors::{BuyingError, OrderError},
+    utils::{is_native_sol, reward_points_for, transfer_tokens, NATIVE_SOL_DECIMALS},
+};
+
+/*
+    Pay Order Share Instruction
+
+    Functionality:
+    - Pays one share of a split-bill order from the signer's wallet
+    - Transfers the share to the restaurant, or to the order escrow when the order is escrowed
+      (an escrow token account for tokens, the order account itself for native SOL)
+    - Initializes the payer's Customer account if it doesn't exist, counts the order and accrues
+      reward points for the share at the restaurant's reward_points_per_token rate
+    - The order can only move past Pending once every share is paid
+
+    Security checks:
+    - Only Pending split-bill orders accept share payments
+    - Each share can only be paid once, and each wallet can only pay one share
+    - Validates the payment mint against Restaurant.currency
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct PayOrderShareArgs {
+    order_id: u64,
+    share_index: u8,
+    customer_name: Option<String>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: PayOrderShareArgs)]
+pub struct PayOrderShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"order", restaurant.key().as_ref(), args.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Account<'info, CustomerOrder>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = Customer::INIT_SPACE,
+        seeds = [b"customer", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub customer: Account<'info, Customer>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub signer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", order.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PayOrderShare<'info> {
+    pub fn pay_order_share(&mut self, args: PayOrderShareArgs, customer_bump: u8) -> Result<()> {
+        require!(self.order.status == StatusType::Pending, OrderError::InvalidStatusTransition);
+
+        let payer = self.signer.key();
+        let index = args.share_index as usize;
+        require!(index < self.order.shares.len(), OrderError::InvalidShare);
+        require!(self.order.shares[index].payer.is_none(), OrderError::ShareAlreadyPaid);
+        require!(
+            self.order.shares.iter().all(|share| share.payer != Some(payer)),
+            OrderError::PayerAlreadyPaid
+        );
+
+        let amount = self.order.shares[index].amount;
+        self.transfer_share(amount)?;
+
+        let decimals = match is_native_sol(&self.restaurant.currency) {
+            true => NATIVE_SOL_DECIMALS,
+            false => self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.decimals,
+        };
+        let reward_points = reward_points_for(amount, self.restaurant.reward_points_per_token, decimals)?;
+        self.initialize_or_update_customer(args.customer_name, reward_points, customer_bump)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let share = &mut self.order.shares[index];
+        share.payer = Some(payer);
+        share.reward_points = reward_points;
+        share.paid_at = Some(now);
+
+        self.order.paid = self.order.paid.checked_add(amount).ok_or(BuyingError::Overflow)?;
+        self.order.updated_at = Some(now);
+
+        emit!(OrderSharePaid {
+            order_id: self.order.order_id,
+            payer,
+            share_index: args.share_index,
+            amount,
+            reward_points,
+            settled: self.order.is_settled(),
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    fn initialize_or_update_customer(&mut self, customer_name: Option<String>, reward_points: u64, customer_bump: u8) -> Result<()> {
+        if !self.customer.initialized {
+            self.customer.set_inner(Customer::new(
+                customer_name,
+                self.restaurant.key(),
+                Clock::get()?.unix_timestamp,
+                1,
+                reward_points,
+                customer_bump,
+            ));
+        } else {
+            self.customer.total_orders = self.customer.total_orders
+                .checked_add(1)
+                .ok_or(BuyingError::Overflow)?;
+            self.customer.reward_points = self.customer.reward_points
+                .checked_add(reward_points)
+                .ok_or(BuyingError::Overflow)?;
+        }
+
+        Ok(())
+    }
+
+    fn transfer_share(&self, amount: u64) -> Result<()> {
+        let escrowed = self.order.escrow_expires_at.is_some();
+
+        if is_native_sol(&self.restaurant.currency) {
+            // Escrowed SOL is held by the order account itself
+            let to = match escrowed {
+                true => self.order.to_account_info(),
+                false => self.restaurant.to_account_info(),
+            };
+
+            return transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.signer.to_account_info(),
+                        to,
+                    }
+                ),
+                amount,
+            );
+        }
+
+        let to = match escrowed {
+            true => self.escrow.as_ref().ok_or(OrderError::MissingEscrow)?.to_account_info(),
+            false => self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+        };
+
+        transfer_tokens(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.signer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+            to,
+            self.signer.to_account_info(),
+            &[],
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<PayOrderShare>, args: PayOrderShareArgs) -> Result<()> {
+    let customer_bump = ctx.bumps.customer;
+
+    ctx.accounts.pay_order_share(args, customer_bump)
+}
+
+#[event]
+pub struct OrderSharePaid {
+    pub order_id: u64,
+    pub payer: Pubkey,
+    pub share_index: u8,
+    pub amount: u64,
+    pub reward_points: u64,
+    pub settled: bool,
+    pub restaurant: Pubkey,
+}
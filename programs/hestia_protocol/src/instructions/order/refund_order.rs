@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    state::{Customer, CustomerOrder, Restaurant, StatusType, Employee},
+    errors::{BuyingError, OrderError},
+    utils::{is_native_sol, transfer_program_lamports, transfer_tokens},
+};
+
+/*
+    Refund Customer Order Instruction
+
+    Functionality:
+    - Allows a restaurant employee to refund part (or all) of an order's on-chain payment
+    - Transfers the refund from the restaurant's ATA, signed by the restaurant PDA, to the customer's ATA
+      (or from the restaurant account to the customer's wallet for native SOL)
+    - Split-bill orders are refunded one share at a time, to the wallet that paid the share
+    - Marks the order as Refunded once the full payment has been returned
+    - Removes the reward points earned by the order once it is fully refunded (by the share once a share is)
+
+    Security checks:
+    - Ensures the signer is a valid restaurant employee with the role required to refund
+    - Only Completed or Finalized orders can be refunded, cancellations refund through cancel order
+    - The refund can never exceed what was paid minus what was already refunded (per share for split bills)
+    - The refund destination and Customer account must belong to the customer, or to the share's payer
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RefundOrderArgs {
+    order_id: u64,
+    amount: u64,
+    share_index: Option<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RefundOrderArgs)]
+pub struct RefundCustomerOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"order", restaurant.key().as_ref(), args.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )] 
+    pub order: Account<'info, CustomerOrder>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub customer: Account<'info, Customer>,
+
+    #[account(mut)] 
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Account<'info, Employee>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = currency,
+        token::token_program = token_program,
+    )]
+    pub customer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub customer_wallet: Option<SystemAccount<'info>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundCustomerOrder<'info> {
+    pub fn refund_order(&mut self, amount: u64, share_index: Option<u8>) -> Result<()> {
+        let required_role = self.order.status
+            .transition_role(&StatusType::Refunded)
+            .ok_or(OrderError::InvalidStatusTransition)?;
+        require!(self.employee.employee_type >= required_role, OrderError::Unauthorized);
+
+        require!(amount > 0, OrderError::InvalidRefundAmount);
+        let refunded = self.order.refunded
+            .checked_add(amount)
+            .ok_or(BuyingError::Overflow)?;
+        require!(refunded <= self.order.paid, OrderError::InvalidRefundAmount);
+
+        // Reward points are only taken back once the payment they were earned with is fully refunded
+        let (recipient, reward_points) = match self.order.shares.is_empty() {
+            true => match refunded == self.order.paid {
+                true => (self.order.customer, self.order.reward_points),
+                false => (self.order.customer, 0),
+            },
+            false => self.refund_share(share_index, amount)?,
+        };
+        self.check_customer(recipient)?;
+        self.transfer_refund(recipient, amount)?;
+
+        self.customer.reward_points = self.customer.reward_points.saturating_sub(reward_points);
+
+        self.order.refunded = refunded;
+        self.order.updated_at = Some(Clock::get()?.unix_timestamp);
+        if self.order.refunded == self.order.paid {
+            self.order.status = StatusType::Refunded;
+        }
+
+        emit!(OrderRefunded {
+            order_id: self.order.order_id,
+            customer: recipient,
+            amount,
+            total_refunded: self.order.refunded,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Records `amount` against the refunded share and returns the wallet that paid it,
+    /// with the share's reward points once it is fully refunded
+    fn refund_share(&mut self, share_index: Option<u8>, amount: u64) -> Result<(Pubkey, u64)> {
+        let share = self.order.shares
+            .get_mut(share_index.ok_or(OrderError::InvalidShare)? as usize)
+            .ok_or(OrderError::InvalidShare)?;
+        let payer = share.payer.ok_or(OrderError::InvalidShare)?;
+
+        let refunded = share.refunded
+            .checked_add(amount)
+            .ok_or(BuyingError::Overflow)?;
+        require!(refunded <= share.amount, OrderError::InvalidRefundAmount);
+        share.refunded = refunded;
+
+        match refunded == share.amount {
+            true => Ok((payer, share.reward_points)),
+            false => Ok((payer, 0)),
+        }
+    }
+
+    /// The Customer account must be the refund recipient's for this restaurant
+    fn check_customer(&self, recipient: Pubkey) -> Result<()> {
+        let restaurant_key = self.restaurant.key();
+        let expected_customer = Pubkey::create_program_address(
+            &[b"customer", restaurant_key.as_ref(), recipient.as_ref(), &[self.customer.bump]],
+            &crate::ID,
+        ).map_err(|_| OrderError::InvalidCustomer)?;
+        require_keys_eq!(self.customer.key(), expected_customer, OrderError::InvalidCustomer);
+
+        Ok(())
+    }
+
+    fn transfer_refund(&self, recipient: Pubkey, amount: u64) -> Result<()> {
+        if is_native_sol(&self.restaurant.currency) {
+            let customer_wallet = self.customer_wallet.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+            require_keys_eq!(customer_wallet.key(), recipient, OrderError::InvalidCustomer);
+            return transfer_program_lamports(&self.restaurant.to_account_info(), &customer_wallet.to_account_info(), amount);
+        }
+
+        let customer_ata = self.customer_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+        require_keys_eq!(customer_ata.owner, recipient, OrderError::InvalidCustomer);
+
+        let owner = self.restaurant.owner;
+        let signer_seeds: &[&[u8]; 3] = &[b"restaurant", owner.as_ref(), &[self.restaurant.bump]];
+
+        transfer_tokens(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+            customer_ata.to_account_info(),
+            self.restaurant.to_account_info(),
+            &[signer_seeds],
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<RefundCustomerOrder>, args: RefundOrderArgs) -> Result<()> {
+    ctx.accounts.refund_order(args.amount, args.share_index)
+}
+
+#[event]
+pub struct OrderRefunded {
+    pub order_id: u64,
+    pub customer: Pubkey,
+    pub amount: u64,
+    pub total_refunded: u64,
+    pub restaurant: Pubkey,
+}
@@ -1,19 +1,28 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
-    state::{CustomerOrder, Restaurant, StatusType, Employee, EmployeeType},
-    errors::OrderError
+    state::{CustomerOrder, OrderLine, Restaurant, StatusType, Employee},
+    errors::{BuyingError, OrderError},
+    utils::{close_empty_token_account, is_native_sol, transfer_program_lamports, transfer_tokens},
 };
 
 /*
     Update Customer Order Instruction
 
     Functionality:
-    - Allows a restaurant employee to update the status of an order
+    - Allows a restaurant employee to move an order through its status lifecycle
+    - Pending -> Accepted -> Preparing -> Ready -> Completed -> Finalized
     - Updates the order's status and timestamp
+    - Releases an escrowed payment to the restaurant once the order is Completed or Finalized
+    - Closes the emptied escrow token account, returning its rent to the customer
 
     Security checks:
     - Ensures the signer is a valid restaurant employee
     - Verifies that the order belongs to the correct restaurant
+    - Rejects illegal transitions and gates each transition by EmployeeType
+    - Cancellations must go through the cancel order instruction so stock is restored
+    - Refunds must go through the refund order instruction so the payment is returned
+    - Split-bill orders can't move past Pending until every share is paid
 */
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -41,18 +50,104 @@ pub struct UpdateCustomerOrder<'info> {
     #[account(
         seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
         bump = employee.bump,
-        constraint = employee.employee_type != EmployeeType::TeamMember @ OrderError::Unauthorized,
     )]
     pub employee: Account<'info, Employee>,
 
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", order.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = order.customer @ OrderError::InvalidCustomer,
+    )]
+    pub customer_wallet: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> UpdateCustomerOrder<'info> {
     pub fn update_order(&mut self, status: StatusType) -> Result<()> {
+        require!(status != StatusType::Cancelled, OrderError::CancelRequiresCancelOrder);
+        require!(status != StatusType::Refunded, OrderError::RefundRequiresRefundOrder);
+        require!(self.order.is_settled(), OrderError::OrderNotSettled);
+
+        let required_role = self.order.status
+            .transition_role(&status)
+            .ok_or(OrderError::InvalidStatusTransition)?;
+        require!(self.employee.employee_type >= required_role, OrderError::Unauthorized);
+
         self.order.status = status;
         self.order.updated_at = Some(Clock::get()?.unix_timestamp);
 
+        let settled = matches!(self.order.status, StatusType::Completed | StatusType::Finalized);
+        if settled && self.order.escrow_expires_at.is_some() {
+            self.release_escrow()?;
+        }
+
+        Ok(())
+    }
+
+    fn release_escrow(&mut self) -> Result<()> {
+        let restaurant_key = self.restaurant.key();
+
+        // Escrowed SOL is held by the order account itself
+        let amount = if is_native_sol(&self.restaurant.currency) {
+            let amount = self.order.paid.saturating_sub(self.order.refunded);
+            transfer_program_lamports(&self.order.to_account_info(), &self.restaurant.to_account_info(), amount)?;
+            amount
+        } else {
+            let token_program = self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+            let customer_wallet = self.customer_wallet.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?;
+            let escrow = self.escrow.as_mut().ok_or(OrderError::MissingEscrow)?;
+            let amount = escrow.amount;
+
+            let order_id = self.order.order_id.to_le_bytes();
+            let signer_seeds: &[&[u8]; 4] = &[b"order", restaurant_key.as_ref(), order_id.as_ref(), &[self.order.bump]];
+
+            transfer_tokens(
+                token_program,
+                self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                escrow.to_account_info(),
+                self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+                self.order.to_account_info(),
+                &[signer_seeds],
+                amount,
+            )?;
+            close_empty_token_account(
+                token_program,
+                escrow,
+                customer_wallet.to_account_info(),
+                self.order.to_account_info(),
+                &[signer_seeds],
+            )?;
+            amount
+        };
+
+        self.order.escrow_expires_at = None;
+
+        emit!(EscrowReleased {
+            order_id: self.order.order_id,
+            amount,
+            restaurant: restaurant_key,
+        });
+
         Ok(())
     }
 }
@@ -61,23 +156,23 @@ pub fn handler(ctx: Context<UpdateCustomerOrder>, args: UpdateOrderArgs) -> Resu
     let status_type = StatusType::from_u8(args.status)
         .ok_or(OrderError::InvalidStatusType)?;
 
-    
+    ctx.accounts.update_order(status_type)?;
 
     let order = &ctx.accounts.order;
 
-    // Emit the new order event
+    // Emit the order updated event
     emit!(OrderUpdated {
         order_id: order.order_id,
         customer: order.customer,
-        items: order.items.clone(),
+        lines: order.lines.clone(),
         total: order.total,
-        status: StatusType::Pending,
+        status: order.status.clone(),
         created_at: order.created_at,
         updated_at: Clock::get()?.unix_timestamp,
         restaurant: ctx.accounts.restaurant.key(),
     });
 
-    ctx.accounts.update_order(status_type)
+    Ok(())
 }
 
 impl StatusType {
@@ -87,6 +182,10 @@ impl StatusType {
             1 => Some(Self::Completed),
             2 => Some(Self::Finalized),
             3 => Some(Self::Cancelled),
+            4 => Some(Self::Accepted),
+            5 => Some(Self::Preparing),
+            6 => Some(Self::Ready),
+            7 => Some(Self::Refunded),
             _ => None,
         }
     }
@@ -96,10 +195,17 @@ impl StatusType {
 pub struct OrderUpdated {
     pub order_id: u64,
     pub customer: Pubkey,
-    pub items: Vec<u64>,
+    pub lines: Vec<OrderLine>,
     pub total: u64,
     pub status: StatusType,
     pub created_at: i64,
     pub updated_at: i64,
     pub restaurant: Pubkey,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct EscrowReleased {
+    pub order_id: u64,
+    pub amount: u64,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, MenuCategoryType, Promotion, PromotionRule, Restaurant},
+    errors::PromotionError,
+    constants::BPS_DENOMINATOR,
+};
+
+/*
+    Create Promotion Instruction
+
+    Functionality:
+    - Creates a Promotion account for a promo code, scoped to the restaurant
+    - Supports percent-off (in basis points), fixed-off (in currency base units) and buy-X-get-Y rules
+    - Promotions run between starts_at and ends_at, and can cap total and per-customer redemptions
+    - An optional MenuCategoryType limits the discount to items of that category
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Validates the promo code length, the rule parameters and the schedule
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreatePromotionArgs {
+    code: String,
+    rule: PromotionRule,
+    category: Option<u8>,
+    starts_at: i64,
+    ends_at: i64,
+    max_redemptions: Option<u32>,
+    per_customer_limit: Option<u16>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreatePromotionArgs)]
+pub struct CreatePromotion<'info> {
+    #[account(
+        init,
+        payer = restaurant_admin,
+        space = Promotion::INIT_SPACE,
+        seeds = [b"promotion", restaurant.key().as_ref(), args.code.as_bytes()],
+        bump,
+    )]
+    pub promotion: Account<'info, Promotion>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ PromotionError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatePromotion<'info> {
+    pub fn create_promotion(&mut self, args: CreatePromotionArgs, bump: u8) -> Result<()> {
+        require!(
+            !args.code.is_empty() && args.code.len() <= Promotion::MAX_CODE_LEN,
+            PromotionError::InvalidCode
+        );
+        require!(args.starts_at < args.ends_at, PromotionError::InvalidSchedule);
+
+        let valid_rule = match args.rule {
+            PromotionRule::PercentOff { bps } => bps > 0 && bps as u64 <= BPS_DENOMINATOR,
+            PromotionRule::FixedOff { amount } => amount > 0,
+            PromotionRule::BuyXGetY { buy, get } => buy > 0 && get > 0,
+        };
+        require!(valid_rule, PromotionError::InvalidRule);
+        require!(
+            args.max_redemptions != Some(0) && args.per_customer_limit != Some(0),
+            PromotionError::InvalidRule
+        );
+
+        let category = match args.category {
+            Some(category) => Some(MenuCategoryType::from_u8(category).ok_or(PromotionError::InvalidCategory)?),
+            None => None,
+        };
+
+        self.promotion.set_inner(Promotion {
+            restaurant: self.restaurant.key(),
+            code: args.code,
+            rule: args.rule,
+            category,
+            starts_at: args.starts_at,
+            ends_at: args.ends_at,
+            max_redemptions: args.max_redemptions,
+            per_customer_limit: args.per_customer_limit,
+            redemptions: 0,
+            active: true,
+            bump,
+        });
+
+        emit!(PromotionCreated {
+            code: self.promotion.code.clone(),
+            rule: self.promotion.rule.clone(),
+            starts_at: self.promotion.starts_at,
+            ends_at: self.promotion.ends_at,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreatePromotion>, args: CreatePromotionArgs) -> Result<()> {
+    ctx.accounts.create_promotion(args, ctx.bumps.promotion)
+}
+
+// Event emitted when a promotion is created
+#[event]
+pub struct PromotionCreated {
+    pub code: String,
+    pub rule: PromotionRule,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub restaurant: Pubkey,
+}
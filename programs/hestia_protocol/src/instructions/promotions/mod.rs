@@ -0,0 +1,5 @@
+pub mod create_promotion;
+pub use create_promotion::*;
+
+pub mod toggle_promotion;
+pub use toggle_promotion::*;
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::{state::{AdminProfile, Promotion, Restaurant}, errors::PromotionError};
+
+/*
+    Toggle Promotion Instruction
+
+    Functionality:
+    - Toggles the active status of a promotion (pauses or resumes it)
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The promotion is derived from the restaurant and its promo code
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct TogglePromotionArgs {
+    code: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: TogglePromotionArgs)]
+pub struct TogglePromotion<'info> {
+    #[account(
+        mut,
+        seeds = [b"promotion", restaurant.key().as_ref(), args.code.as_bytes()],
+        bump = promotion.bump,
+    )]
+    pub promotion: Account<'info, Promotion>,
+
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ PromotionError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> TogglePromotion<'info> {
+    pub fn toggle(&mut self) -> Result<()> {
+        self.promotion.active = !self.promotion.active;
+
+        emit!(PromotionToggled {
+            code: self.promotion.code.clone(),
+            new_status: self.promotion.active,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<TogglePromotion>, _args: TogglePromotionArgs) -> Result<()> {
+    ctx.accounts.toggle()
+}
+
+// Event emitted when a promotion's status is toggled
+#[event]
+pub struct PromotionToggled {
+    pub code: String,
+    pub new_status: bool,
+    pub restaurant: Pubkey,
+}
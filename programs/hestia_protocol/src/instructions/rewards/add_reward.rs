@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::{
-    errors::RewardError,
-    state::{AdminProfile, Manager, MenuCategoryType, Restaurant, RewardVoucher}
+    errors::{BuyingError, RewardError},
+    state::{AdminProfile, Manager, MenuCategoryType, MenuItem, Restaurant, RewardVoucher}
 };
 use mpl_core::accounts::BaseCollectionV1;
 
@@ -10,18 +10,20 @@ use mpl_core::accounts::BaseCollectionV1;
 
     Functionality:
     - Creates a new RewardVoucher account for a specific reward
-    - Links the voucher to a menu item and sets its properties
+    - Links the voucher to a menu item of the restaurant (by SKU) and sets its properties
+    - Counts the voucher on the menu item so the item can't be removed while the voucher is active
 
     Security checks:
     - Ensures the signer is the restaurant admin
     - Verifies that the restaurant belongs to the admin
     - Checks that the reward's update authority is the manager
+    - The menu item must exist under the restaurant-scoped menu item address
 */
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct AddRewardVoucherArgs {
     pub id: u64,
-    pub item_sku: u64,
+    pub item_sku: String,
     pub reward: Pubkey,
     pub category: u8,
     pub share: u16,
@@ -55,10 +57,17 @@ pub struct AddRewardVoucher<'info> {
     #[account(constraint = reward.update_authority == manager.key() @ RewardError::InvalidRewardAuthority)] 
     pub reward: Account<'info, BaseCollectionV1>,
 
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.item_sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
     #[account(
         init,
         payer = restaurant_admin,
-        space = RewardVoucher::INIT_SPACE,
+        space = RewardVoucher::INIT_SPACE + args.item_sku.len(),
         seeds = [b"voucher", reward.key().as_ref()],
         bump,
     )] 
@@ -84,6 +93,10 @@ impl<'info> AddRewardVoucher<'info> {
             starting_time: args.starting_time,
             bump,
         });
+
+        self.menu_item.active_vouchers = self.menu_item.active_vouchers
+            .checked_add(1)
+            .ok_or(BuyingError::Overflow)?;
         
         Ok(())
     } 
@@ -94,7 +107,7 @@ pub fn handler(ctx: Context<AddRewardVoucher>, args: AddRewardVoucherArgs) -> Re
 }
 
 impl MenuCategoryType {
-    fn from_u8(value: u8) -> Option<Self> {
+    pub(crate) fn from_u8(value: u8) -> Option<Self> {
         match value {
             0 => Some(Self::Combo),
             1 => Some(Self::Side),
@@ -0,0 +1,148 @@
+use anchor_lang::{prelude::*, Discriminator};
+use crate::{
+    state::{AdminProfile, MenuCategoryType, MenuItem, Restaurant, RewardVoucher},
+    errors::{BuyingError, RewardError},
+    utils::resize_program_account,
+};
+
+/*
+    Migrate Reward Voucher Instruction
+
+    Functionality:
+    - Converts an active legacy RewardVoucher, which stored a numeric item_sku, to the current layout that
+      references the restaurant's MenuItem by its SKU
+    - Resizes the voucher account in place, the restaurant admin pays or receives the rent difference
+    - Counts the voucher in the menu item's active_vouchers, so the item can't be removed while it is live;
+      legacy vouchers can't be bought or removed until they are migrated
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The voucher must be an active legacy RewardVoucher of the restaurant
+    - The menu item must exist under the restaurant-scoped menu item address
+*/
+
+// Legacy RewardVoucher: id, item_sku (u64), reward, restaurant, category, share, share_sold, price, starting_time, bump
+const LEGACY_REWARD_VOUCHER_SPACE: usize = 8 + 8 + 8 + 32 + 32 + MenuCategoryType::INIT_SPACE + 2 + 2 + 8 + 8 + 1;
+
+#[derive(AnchorDeserialize)]
+struct LegacyRewardVoucher {
+    id: u64,
+    _item_sku: u64,
+    reward: Pubkey,
+    restaurant: Pubkey,
+    category: MenuCategoryType,
+    share: u16,
+    share_sold: u16,
+    price: u64,
+    starting_time: i64,
+    bump: u8,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MigrateRewardVoucherArgs {
+    reward: Pubkey,
+    item_sku: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MigrateRewardVoucherArgs)]
+pub struct MigrateRewardVoucher<'info> {
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ RewardError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"voucher", args.reward.as_ref()],
+        bump,
+    )]
+    /// CHECK: Legacy RewardVoucher layout, deserialized in the instruction
+    pub voucher: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), args.item_sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateRewardVoucher<'info> {
+    pub fn migrate_reward_voucher(&mut self, item_sku: String) -> Result<()> {
+        let legacy = {
+            let data = self.voucher.try_borrow_data()?;
+            require!(
+                data.len() == LEGACY_REWARD_VOUCHER_SPACE && data[..8] == RewardVoucher::DISCRIMINATOR,
+                RewardError::NotALegacyVoucher
+            );
+            // A migrated voucher with a 4 byte SKU has the legacy size, its SKU is printable
+            let migrated = RewardVoucher::try_deserialize(&mut &data[..])
+                .is_ok_and(|voucher| !voucher.item_sku.contains('\0'));
+            require!(!migrated, RewardError::NotALegacyVoucher);
+            LegacyRewardVoucher::deserialize(&mut &data[8..]).map_err(|_| RewardError::NotALegacyVoucher)?
+        };
+        require_keys_eq!(legacy.restaurant, self.restaurant.key(), RewardError::InvalidRestaurant);
+
+        let voucher = RewardVoucher {
+            id: legacy.id,
+            item_sku,
+            reward: legacy.reward,
+            restaurant: legacy.restaurant,
+            category: legacy.category,
+            share: legacy.share,
+            share_sold: legacy.share_sold,
+            price: legacy.price,
+            starting_time: legacy.starting_time,
+            bump: legacy.bump,
+        };
+
+        let voucher_info = self.voucher.to_account_info();
+        resize_program_account(
+            &voucher_info,
+            RewardVoucher::INIT_SPACE + voucher.item_sku.len(),
+            &self.restaurant_admin.to_account_info(),
+            &self.system_program.to_account_info(),
+        )?;
+        voucher.try_serialize(&mut &mut voucher_info.try_borrow_mut_data()?[..])?;
+
+        self.menu_item.active_vouchers = self.menu_item.active_vouchers
+            .checked_add(1)
+            .ok_or(BuyingError::Overflow)?;
+
+        emit!(RewardVoucherMigrated {
+            voucher: self.voucher.key(),
+            voucher_id: voucher.id,
+            item_sku: voucher.item_sku,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateRewardVoucher>, args: MigrateRewardVoucherArgs) -> Result<()> {
+    ctx.accounts.migrate_reward_voucher(args.item_sku)
+}
+
+// Event emitted when a legacy reward voucher is linked to its restaurant's menu item
+#[event]
+pub struct RewardVoucherMigrated {
+    pub voucher: Pubkey,
+    pub voucher_id: u64,
+    pub item_sku: String,
+    pub restaurant: Pubkey,
+}
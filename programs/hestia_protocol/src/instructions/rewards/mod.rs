@@ -5,4 +5,10 @@ pub mod remove_reward;
 pub use remove_reward::*;
 
 pub mod create_reward;
-pub use create_reward::*;
\ No newline at end of file
+pub use create_reward::*;
+
+pub mod update_reward_rate;
+pub use update_reward_rate::*;
+
+pub mod migrate_reward;
+pub use migrate_reward::*;
\ No newline at end of file
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{AdminProfile, Manager, Restaurant, RewardVoucher};
+use crate::state::{AdminProfile, Manager, MenuItem, Restaurant, RewardVoucher};
 use crate::errors::RewardError;
 use mpl_core::accounts::BaseCollectionV1;
 
@@ -9,11 +9,13 @@ use mpl_core::accounts::BaseCollectionV1;
     Functionality:
     - Removes a reward voucher from the restaurant's reward system
     - Closes the RewardVoucher account and returns the rent to the restaurant admin
+    - Releases the voucher's hold on its menu item
 
     Security checks:
     - Ensures the signer is the restaurant admin
     - Verifies that the restaurant belongs to the admin
     - Checks that the reward's update authority is the manager
+    - Ensures the voucher belongs to the restaurant
 */
 
 #[derive(Accounts)]
@@ -46,9 +48,17 @@ pub struct RemoveRewardVoucher<'info> {
         close = restaurant_admin,
         seeds = [b"voucher", reward.key().as_ref()],
         bump = voucher.bump,
+        constraint = voucher.restaurant == restaurant.key() @ RewardError::InvalidRestaurant,
     )] 
     pub voucher: Account<'info, RewardVoucher>,
 
+    #[account(
+        mut,
+        seeds = [b"menu_item", restaurant.key().as_ref(), voucher.item_sku.as_bytes()],
+        bump = menu_item.bump,
+    )]
+    pub menu_item: Account<'info, MenuItem>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -56,6 +66,8 @@ impl<'info> RemoveRewardVoucher<'info> {
     pub fn remove_reward_voucher(&mut self) -> Result<()> {
         // The actual removal is handled by the `close = restaurant_admin` constraint
         // We can perform any additional cleanup or logging here if needed
+        self.menu_item.active_vouchers = self.menu_item.active_vouchers.saturating_sub(1);
+
         emit!(RewardVoucherRemoved {
             voucher_id: self.voucher.id,
             restaurant: self.restaurant.key(),
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminProfile, Restaurant};
+use crate::errors::RewardError;
+
+/*
+    Update Reward Rate Instruction
+
+    Functionality:
+    - Sets the number of reward points customers earn per whole currency token spent
+    - Points accrue on the base-unit order amount, so fractional token payments earn fractional points (rounded down)
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+*/
+
+#[derive(Accounts)]
+pub struct UpdateRewardRate<'info> {
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump = restaurant.bump,
+        constraint = restaurant.owner == restaurant_admin.key() @ RewardError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+}
+
+impl<'info> UpdateRewardRate<'info> {
+    pub fn update_reward_rate(&mut self, reward_points_per_token: u64) -> Result<()> {
+        self.restaurant.reward_points_per_token = reward_points_per_token;
+
+        emit!(RewardRateUpdated {
+            restaurant: self.restaurant.key(),
+            reward_points_per_token,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateRewardRate>, reward_points_per_token: u64) -> Result<()> {
+    ctx.accounts.update_reward_rate(reward_points_per_token)
+}
+
+// Event emitted when a restaurant changes its reward accrual rate
+#[event]
+pub struct RewardRateUpdated {
+    pub restaurant: Pubkey,
+    pub reward_points_per_token: u64,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, Restaurant, Supplier},
+    errors::{SetupError, SupplierError},
+};
+
+/*
+    Add/Update Supplier Instruction
+
+    Functionality:
+    - Registers a supplier of the restaurant, or updates an existing one
+    - The supplier wallet receives purchase order payments in Restaurant.currency
+    - Inactive suppliers can't be sent new purchase orders
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Supplier names are capped so the account never needs to be resized
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SupplierArgs {
+    id: u64,
+    name: String,
+    wallet: Pubkey,
+    active: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(args: SupplierArgs)]
+pub struct ManageSupplier<'info> {
+    #[account(
+        init_if_needed,
+        payer = restaurant_admin,
+        space = Supplier::INIT_SPACE + Supplier::MAX_NAME_LEN,
+        seeds = [b"supplier", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub supplier: Account<'info, Supplier>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ManageSupplier<'info> {
+    pub fn manage_supplier(&mut self, args: SupplierArgs, bump: u8) -> Result<()> {
+        require!(
+            !args.name.is_empty() && args.name.len() <= Supplier::MAX_NAME_LEN,
+            SupplierError::InvalidName
+        );
+
+        self.supplier.set_inner(Supplier {
+            restaurant: self.restaurant.key(),
+            id: args.id,
+            name: args.name,
+            wallet: args.wallet,
+            active: args.active,
+            bump,
+        });
+
+        emit!(SupplierUpdated {
+            supplier: self.supplier.key(),
+            id: self.supplier.id,
+            name: self.supplier.name.clone(),
+            wallet: self.supplier.wallet,
+            active: self.supplier.active,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ManageSupplier>, args: SupplierArgs) -> Result<()> {
+    ctx.accounts.manage_supplier(args, ctx.bumps.supplier)
+}
+
+// Event emitted when a supplier is registered or updated
+#[event]
+pub struct SupplierUpdated {
+    pub supplier: Pubkey,
+    pub id: u64,
+    pub name: String,
+    pub wallet: Pubkey,
+    pub active: bool,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AdminProfile, InventoryItem, PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus, Restaurant, Supplier},
+    errors::{BuyingError, SetupError, SupplierError},
+};
+
+/*
+    Create Purchase Order Instruction
+
+    Functionality:
+    - Records an order to a supplier: the inventory items, quantities and agreed unit costs
+    - The total is the agreed cost of every line, in currency base units
+    - Stock only moves when the purchase order is received
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The supplier must belong to the restaurant and be active
+    - Every line must reference an InventoryItem of the restaurant, with a positive quantity
+
+    Remaining accounts:
+    - One InventoryItem per line, in the same order as the lines
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct PurchaseOrderLineArgs {
+    quantity: u64,
+    unit_cost: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreatePurchaseOrderArgs {
+    id: u64,
+    supplier_id: u64,
+    lines: Vec<PurchaseOrderLineArgs>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreatePurchaseOrderArgs)]
+pub struct CreatePurchaseOrder<'info> {
+    #[account(
+        init,
+        payer = restaurant_admin,
+        space = PurchaseOrder::INIT_SPACE + args.lines.len() * PurchaseOrderLine::INIT_SPACE,
+        seeds = [b"purchase_order", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub purchase_order: Account<'info, PurchaseOrder>,
+
+    #[account(
+        seeds = [b"supplier", restaurant.key().as_ref(), args.supplier_id.to_le_bytes().as_ref()],
+        bump = supplier.bump,
+        constraint = supplier.active @ SupplierError::InactiveSupplier,
+    )]
+    pub supplier: Account<'info, Supplier>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatePurchaseOrder<'info> {
+    pub fn create_purchase_order(
+        &mut self,
+        args: CreatePurchaseOrderArgs,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=PurchaseOrder::MAX_LINES).contains(&args.lines.len())
+                && args.lines.iter().all(|line| line.quantity > 0),
+            SupplierError::InvalidPurchaseOrder
+        );
+        require!(remaining_accounts.len() == args.lines.len(), SupplierError::InvalidInventoryAccounts);
+
+        let mut lines = Vec::with_capacity(args.lines.len());
+        let mut total: u64 = 0;
+        for (line, inventory_info) in args.lines.iter().zip(remaining_accounts.iter()) {
+            // Inventory items don't record their restaurant, their address does
+            let inventory_item = Account::<InventoryItem>::try_from(inventory_info)?;
+            let restaurant_key = self.restaurant.key();
+            let expected = Pubkey::create_program_address(
+                &[b"inventory".as_ref(), restaurant_key.as_ref(), inventory_item.sku.as_ref(), &[inventory_item.bump]],
+                &crate::ID,
+            ).map_err(|_| SupplierError::InvalidInventoryAccounts)?;
+            require_keys_eq!(inventory_item.key(), expected, SupplierError::InvalidInventoryAccounts);
+
+            total = line.quantity
+                .checked_mul(line.unit_cost)
+                .and_then(|cost| total.checked_add(cost))
+                .ok_or(BuyingError::Overflow)?;
+
+            lines.push(PurchaseOrderLine {
+                inventory_item: inventory_item.key(),
+                quantity: line.quantity,
+                unit_cost: line.unit_cost,
+                received: 0,
+            });
+        }
+
+        self.purchase_order.set_inner(PurchaseOrder {
+            restaurant: self.restaurant.key(),
+            supplier: self.supplier.key(),
+            id: args.id,
+            lines,
+            total,
+            paid: 0,
+            status: PurchaseOrderStatus::Open,
+            created_at: Clock::get()?.unix_timestamp,
+            received_at: None,
+            bump,
+        });
+
+        emit!(PurchaseOrderCreated {
+            purchase_order: self.purchase_order.key(),
+            id: args.id,
+            supplier: self.supplier.key(),
+            lines: self.purchase_order.lines.clone(),
+            total,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CreatePurchaseOrder<'info>>, args: CreatePurchaseOrderArgs) -> Result<()> {
+    ctx.accounts.create_purchase_order(args, ctx.remaining_accounts, ctx.bumps.purchase_order)
+}
+
+// Event emitted when a purchase order is sent to a supplier
+#[event]
+pub struct PurchaseOrderCreated {
+    pub purchase_order: Pubkey,
+    pub id: u64,
+    pub supplier: Pubkey,
+    pub lines: Vec<PurchaseOrderLine>,
+    pub total: u64,
+    pub restaurant: Pubkey,
+}
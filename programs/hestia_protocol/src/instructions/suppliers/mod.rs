@@ -0,0 +1,11 @@
+pub mod add_supplier;
+pub use add_supplier::*;
+
+pub mod create_purchase_order;
+pub use create_purchase_order::*;
+
+pub mod receive_purchase_order;
+pub use receive_purchase_order::*;
+
+pub mod pay_purchase_order;
+pub use pay_purchase_order::*;
\ No newline at end of file
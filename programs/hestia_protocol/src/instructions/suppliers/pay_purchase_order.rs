@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    state::{AdminProfile, PurchaseOrder, Restaurant, Supplier},
+    errors::{BuyingError, SetupError, SupplierError},
+    utils::{check_payout_destination, is_native_sol, transfer_program_lamports, transfer_tokens},
+};
+
+/*
+    Pay Purchase Order Instruction
+
+    Functionality:
+    - Pays the supplier for what has been received on a purchase order and not paid yet
+    - Transfers Restaurant.currency from the restaurant's funds: lamports held by the Restaurant account
+      for native SOL, the restaurant's token account otherwise
+    - Partial deliveries can be paid as they arrive, the purchase order tracks the amount paid
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - The supplier must be the one the purchase order was sent to
+    - The payout destination must belong to the supplier wallet (the wallet itself for native SOL,
+      a Restaurant.currency token account owned by the wallet otherwise)
+
+    Remaining accounts:
+    - The supplier payout destination, writable
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct PayPurchaseOrderArgs {
+    id: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: PayPurchaseOrderArgs)]
+pub struct PayPurchaseOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"purchase_order", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump = purchase_order.bump,
+    )]
+    pub purchase_order: Account<'info, PurchaseOrder>,
+
+    #[account(address = purchase_order.supplier @ SupplierError::InvalidPurchaseOrder)]
+    pub supplier: Account<'info, Supplier>,
+
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        mut,
+        constraint = restaurant.owner == restaurant_admin.key() @ SetupError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = currency,
+        associated_token::authority = restaurant,
+        associated_token::token_program = token_program,
+    )]
+    pub restaurant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl<'info> PayPurchaseOrder<'info> {
+    pub fn pay(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let received_value = self.purchase_order.received_value().ok_or(BuyingError::Overflow)?;
+        let amount = received_value.saturating_sub(self.purchase_order.paid);
+        require!(amount > 0, SupplierError::NothingToPay);
+
+        let destination = remaining_accounts.first().ok_or(BuyingError::InvalidPayoutDestination)?;
+        check_payout_destination(&self.restaurant.currency, destination, &self.supplier.wallet)?;
+
+        if is_native_sol(&self.restaurant.currency) {
+            // Native SOL revenue is held by the Restaurant account itself
+            transfer_program_lamports(&self.restaurant.to_account_info(), destination, amount)?;
+        } else {
+            let owner = self.restaurant.owner;
+            let signer_seeds: &[&[u8]; 3] = &[b"restaurant", owner.as_ref(), &[self.restaurant.bump]];
+
+            transfer_tokens(
+                self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+                self.restaurant_ata.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?.to_account_info(),
+                destination.to_account_info(),
+                self.restaurant.to_account_info(),
+                &[signer_seeds],
+                amount,
+            )?;
+        }
+
+        self.purchase_order.paid = received_value;
+
+        emit!(PurchaseOrderPaid {
+            purchase_order: self.purchase_order.key(),
+            id: self.purchase_order.id,
+            supplier: self.supplier.key(),
+            amount,
+            paid: self.purchase_order.paid,
+            total: self.purchase_order.total,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PayPurchaseOrder<'info>>, _args: PayPurchaseOrderArgs) -> Result<()> {
+    ctx.accounts.pay(ctx.remaining_accounts)
+}
+
+// Event emitted when a supplier is paid for received goods
+#[event]
+pub struct PurchaseOrderPaid {
+    pub purchase_order: Pubkey,
+    pub id: u64,
+    pub supplier: Pubkey,
+    pub amount: u64,
+    pub paid: u64,
+    pub total: u64,
+    pub restaurant: Pubkey,
+}
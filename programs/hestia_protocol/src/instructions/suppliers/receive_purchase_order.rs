@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, EmployeeType, InventoryItem, PurchaseOrder, PurchaseOrderStatus, Restaurant},
+    errors::{BuyingError, SupplierError},
+    utils::receive_lot,
+};
+
+/*
+    Receive Purchase Order Instruction
+
+    Functionality:
+    - Records a delivery against a purchase order, fully or partially
+    - Adds every delivered quantity to its InventoryItem as a new lot, at the line's unit cost and with
+      the expiry printed on the delivery
+    - Moves the purchase order to PartiallyReceived, or Received once every line is complete
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or a Manager (or above) of the restaurant
+    - The purchase order must belong to the restaurant and not be fully received
+    - Delivered quantities can't exceed what is outstanding on each line
+    - Inventory items must match the purchase order lines
+
+    Remaining accounts:
+    - One writable InventoryItem per line with a non-zero delivered quantity, in line order
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ReceivePurchaseOrderArgs {
+    id: u64,
+    lines: Vec<ReceivedLineArgs>, // delivered now, one per purchase order line
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ReceivedLineArgs {
+    quantity: u64,
+    expires_at: Option<i64>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: ReceivePurchaseOrderArgs)]
+pub struct ReceivePurchaseOrder<'info> {
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        mut,
+        seeds = [b"purchase_order", restaurant.key().as_ref(), args.id.to_le_bytes().as_ref()],
+        bump = purchase_order.bump,
+    )]
+    pub purchase_order: Account<'info, PurchaseOrder>,
+}
+
+impl<'info> ReceivePurchaseOrder<'info> {
+    pub fn receive(&mut self, received_lines: Vec<ReceivedLineArgs>, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        // Only the owner or a Manager and above can sign for deliveries
+        let is_manager = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type >= EmployeeType::Manager);
+        require!(self.signer.key() == self.restaurant.owner || is_manager, SupplierError::Unauthorized);
+
+        require!(self.purchase_order.status != PurchaseOrderStatus::Received, SupplierError::AlreadyReceived);
+        require!(
+            received_lines.len() == self.purchase_order.lines.len() && received_lines.iter().any(|line| line.quantity > 0),
+            SupplierError::InvalidReceipt
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut inventory_accounts = remaining_accounts.iter();
+        for (line, received_line) in self.purchase_order.lines.iter_mut().zip(received_lines.iter()) {
+            if received_line.quantity == 0 {
+                continue;
+            }
+
+            let received = line.received.checked_add(received_line.quantity).ok_or(BuyingError::Overflow)?;
+            require!(received <= line.quantity, SupplierError::InvalidReceipt);
+            line.received = received;
+
+            let inventory_info = inventory_accounts.next().ok_or(SupplierError::InvalidInventoryAccounts)?;
+            require_keys_eq!(inventory_info.key(), line.inventory_item, SupplierError::InvalidInventoryAccounts);
+            let mut inventory_item = Account::<InventoryItem>::try_from(inventory_info)?;
+            receive_lot(&mut inventory_item, received_line.quantity, line.unit_cost, received_line.expires_at, now)?;
+            inventory_item.exit(&crate::ID)?;
+        }
+
+        let complete = self.purchase_order.lines.iter().all(|line| line.received == line.quantity);
+        self.purchase_order.status = match complete {
+            true => PurchaseOrderStatus::Received,
+            false => PurchaseOrderStatus::PartiallyReceived,
+        };
+        self.purchase_order.received_at = Some(now);
+
+        emit!(PurchaseOrderReceived {
+            purchase_order: self.purchase_order.key(),
+            id: self.purchase_order.id,
+            quantities: received_lines.iter().map(|line| line.quantity).collect(),
+            status: self.purchase_order.status.clone(),
+            received_by: self.signer.key(),
+            received_at: now,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReceivePurchaseOrder<'info>>, args: ReceivePurchaseOrderArgs) -> Result<()> {
+    ctx.accounts.receive(args.lines, ctx.remaining_accounts)
+}
+
+// Event emitted for every delivery recorded against a purchase order
+#[event]
+pub struct PurchaseOrderReceived {
+    pub purchase_order: Pubkey,
+    pub id: u64,
+    pub quantities: Vec<u64>,
+    pub status: PurchaseOrderStatus,
+    pub received_by: Pubkey,
+    pub received_at: i64,
+    pub restaurant: Pubkey,
+}
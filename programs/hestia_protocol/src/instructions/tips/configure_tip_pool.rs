@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    state::{AdminProfile, Restaurant, TipPool, TipDistribution},
+    errors::{BuyingError, TipError},
+    utils::is_native_sol,
+};
+
+/*
+    Configure Tip Pool Instruction
+
+    Functionality:
+    - Creates the restaurant's TipPool on first use, along with its token vault for SPL / Token-2022 currencies
+      (native SOL tips are held by the TipPool account itself)
+    - Sets how the pool is shared between employees: equally, by role (one weight per EmployeeType)
+      or by hours worked
+    - Reconfiguring keeps the undistributed balance and the running totals
+    - Can't be reconfigured while a distribution round is being tallied or paid out
+
+    Security checks:
+    - Ensures the signer is the restaurant admin
+    - Verifies that the restaurant belongs to the admin
+    - Validates the tip vault mint against Restaurant.currency
+*/
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ConfigureTipPoolArgs {
+    distribution: u8,
+    role_weights: [u16; 4],
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTipPool<'info> {
+    #[account(mut)]
+    pub restaurant_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin", restaurant_admin.key().as_ref()],
+        bump = admin_profile.bump,
+    )]
+    pub admin_profile: Account<'info, AdminProfile>,
+
+    #[account(
+        seeds = [b"restaurant", restaurant_admin.key().as_ref()],
+        bump = restaurant.bump,
+        constraint = restaurant.owner == restaurant_admin.key() @ TipError::Unauthorized,
+    )]
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        init_if_needed,
+        payer = restaurant_admin,
+        space = TipPool::INIT_SPACE,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump,
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = restaurant_admin,
+        seeds = [b"tip_vault", tip_pool.key().as_ref()],
+        bump,
+        token::mint = currency,
+        token::authority = tip_pool,
+        token::token_program = token_program,
+    )]
+    pub tip_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ConfigureTipPool<'info> {
+    pub fn configure_tip_pool(&mut self, distribution: TipDistribution, role_weights: [u16; 4], bump: u8) -> Result<()> {
+        if !is_native_sol(&self.restaurant.currency) {
+            require!(self.tip_vault.is_some(), TipError::MissingTipPool);
+        }
+
+        if self.tip_pool.restaurant == Pubkey::default() {
+            self.tip_pool.set_inner(TipPool {
+                restaurant: self.restaurant.key(),
+                distribution,
+                role_weights,
+                balance: 0,
+                total_collected: 0,
+                total_distributed: 0,
+                on_shift: 0,
+                round: 1,
+                round_weight: 0,
+                round_pool: 0,
+                round_tallied: 0,
+                round_paid: 0,
+                bump,
+            });
+        } else {
+            // Shares already tallied must be weighed the same way as the rest of the round
+            require!(!self.tip_pool.is_distributing(), TipError::DistributionInProgress);
+            self.tip_pool.distribution = distribution;
+            self.tip_pool.role_weights = role_weights;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ConfigureTipPool>, args: ConfigureTipPoolArgs) -> Result<()> {
+    let distribution = match args.distribution {
+        0 => TipDistribution::Equal,
+        1 => TipDistribution::ByRole,
+        2 => TipDistribution::ByHours,
+        _ => return Err(TipError::InvalidDistribution.into()),
+    };
+
+    ctx.accounts.configure_tip_pool(distribution, args.role_weights, ctx.bumps.tip_pool)
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    state::{Restaurant, Employee, EmployeeType, TipPool, TipDistribution, TipPayout},
+    errors::{BuyingError, TipError},
+    utils::{check_payout_destination, is_native_sol, load_tip_shift, transfer_program_lamports, transfer_tokens},
+};
+
+/*
+    Distribute Tips Instruction
+
+    Functionality:
+    - Second step of a distribution round: pays a batch of the Shifts tallied by restaurant_tally_tips,
+      so a round can cover more staff than fit in a single transaction
+    - The round's first payout fixes the amount it shares (the undistributed TipPool balance); each Shift
+      receives that amount times its tallied weight over the round's total weight
+    - Resets the tallied minutes of every paid Shift; minutes worked since the tally carry over
+    - Shifts of removed employees are reset without a payout
+    - Once every tallied Shift is paid the round closes, rounding dust and tips collected during the
+      round stay in the pool for the next one
+    - Emits a TipsDistributed event per batch recording every payout, its minutes worked and its share weight
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or a Manager (or above) of the restaurant
+    - Every on-shift employee (TipPool.on_shift) must be tallied before the first payout, so the pool
+      can't be steered to a subset of the staff
+    - Each tallied Shift is paid once per round
+    - Employee and Shift accounts must be the restaurant's PDAs for the same wallet
+    - Payout destinations must belong to the employee's wallet (the wallet itself for native SOL,
+      a Restaurant.currency token account owned by the wallet otherwise)
+
+    Remaining accounts:
+    - One [employee, shift (writable), destination] triple per tallied Shift of the batch; for a removed
+      employee the employee account is its closed PDA and the destination is ignored
+*/
+
+#[derive(Accounts)]
+pub struct DistributeTips<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump = tip_pool.bump,
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+
+    #[account(address = restaurant.currency @ BuyingError::InvalidCurrency)]
+    pub currency: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_vault", tip_pool.key().as_ref()],
+        bump,
+    )]
+    pub tip_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl<'info> DistributeTips<'info> {
+    pub fn distribute_tips(&mut self, remaining_accounts: &'info [AccountInfo<'info>], now: i64) -> Result<()> {
+        // Only the owner or a Manager and above can pay out the pool
+        let is_manager = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type >= EmployeeType::Manager);
+        require!(self.signer.key() == self.restaurant.owner || is_manager, TipError::Unauthorized);
+
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 3 == 0,
+            TipError::InvalidEmployeeAccounts
+        );
+
+        // The round's first payout fixes what the whole round shares
+        if self.tip_pool.round_paid == 0 {
+            require!(
+                self.tip_pool.is_distributing() && self.tip_pool.round_tallied == self.tip_pool.on_shift,
+                TipError::MissingOnShiftEmployees
+            );
+
+            // Transfer-fee mints can leave the vault holding less than was tipped
+            self.tip_pool.round_pool = match &self.tip_vault {
+                Some(tip_vault) if !is_native_sol(&self.restaurant.currency) => self.tip_pool.balance.min(tip_vault.amount),
+                _ => self.tip_pool.balance,
+            };
+        }
+
+        let restaurant_key = self.restaurant.key();
+        let mut payouts: Vec<TipPayout> = Vec::with_capacity(remaining_accounts.len() / 3);
+        for triple in remaining_accounts.chunks(3) {
+            let (employee, mut shift) = load_tip_shift(&restaurant_key, &triple[0], &triple[1])?;
+            require!(shift.tip_round == self.tip_pool.round && !shift.tip_paid, TipError::NotTallied);
+
+            let amount = match &employee {
+                Some(employee) if self.tip_pool.round_weight > 0 => {
+                    check_payout_destination(&self.restaurant.currency, &triple[2], &employee.wallet)?;
+                    let share = u64::try_from(
+                        self.tip_pool.round_pool as u128 * shift.tip_weight as u128 / self.tip_pool.round_weight as u128
+                    ).map_err(|_| BuyingError::Overflow)?;
+
+                    // Tips refunded since the round started can leave the pool short
+                    share.min(self.tip_pool.balance)
+                }
+                _ => 0,
+            };
+            if amount > 0 {
+                self.pay_out(&triple[2], amount)?;
+                self.tip_pool.balance -= amount;
+            }
+
+            // Tallied minutes are paid out, minutes worked since keep counting
+            shift.unpaid_minutes = shift.unpaid_minutes.saturating_sub(shift.tip_minutes);
+            shift.tip_paid = true;
+            if employee.is_none() {
+                shift.unpaid_minutes = 0;
+                shift.clocked_in_at = None;
+            }
+            if !shift.is_on_shift() {
+                self.tip_pool.on_shift = self.tip_pool.on_shift.saturating_sub(1);
+            }
+            // Written back straight away so a Shift passed twice is caught as already paid
+            shift.exit(&crate::ID)?;
+
+            self.tip_pool.round_paid = self.tip_pool.round_paid.checked_add(1).ok_or(BuyingError::Overflow)?;
+            payouts.push(TipPayout {
+                employee: shift.wallet,
+                minutes_worked: shift.tip_minutes,
+                weight: shift.tip_weight,
+                amount,
+            });
+        }
+
+        let distributed = payouts.iter().map(|payout| payout.amount).sum::<u64>();
+        self.tip_pool.total_distributed = self.tip_pool.total_distributed
+            .checked_add(distributed)
+            .ok_or(BuyingError::Overflow)?;
+
+        let round = self.tip_pool.round;
+        if self.tip_pool.round_paid == self.tip_pool.round_tallied {
+            self.tip_pool.close_round();
+        }
+
+        emit!(TipsDistributed {
+            restaurant: self.restaurant.key(),
+            round,
+            distribution: self.tip_pool.distribution.clone(),
+            amount: distributed,
+            remaining: self.tip_pool.balance,
+            payouts,
+            distributed_by: self.signer.key(),
+            distributed_at: now,
+        });
+
+        Ok(())
+    }
+
+    fn pay_out(&self, destination: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        // Native SOL tips are held by the TipPool account itself
+        if is_native_sol(&self.restaurant.currency) {
+            return transfer_program_lamports(&self.tip_pool.to_account_info(), destination, amount);
+        }
+
+        let restaurant_key = self.restaurant.key();
+        let signer_seeds: &[&[u8]; 3] = &[b"tip_pool", restaurant_key.as_ref(), &[self.tip_pool.bump]];
+
+        transfer_tokens(
+            self.token_program.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.currency.as_ref().ok_or(BuyingError::MissingPaymentAccounts)?,
+            self.tip_vault.as_ref().ok_or(TipError::MissingTipPool)?.to_account_info(),
+            destination.clone(),
+            self.tip_pool.to_account_info(),
+            &[signer_seeds],
+            amount,
+        )
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeTips<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.distribute_tips(ctx.remaining_accounts, now)
+}
+
+// Event emitted for every batch of tip pool payouts, recording each employee's share
+#[event]
+pub struct TipsDistributed {
+    pub restaurant: Pubkey,
+    pub round: u64,
+    pub distribution: TipDistribution,
+    pub amount: u64,
+    pub remaining: u64,
+    pub payouts: Vec<TipPayout>,
+    pub distributed_by: Pubkey,
+    pub distributed_at: i64,
+}
@@ -0,0 +1,11 @@
+pub mod configure_tip_pool;
+pub use configure_tip_pool::*;
+
+pub mod distribute_tips;
+pub use distribute_tips::*;
+
+pub mod tally_tips;
+pub use tally_tips::*;
+
+pub mod shifts;
+pub use shifts::*;
\ No newline at end of file
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Employee, Restaurant, Shift, TipPool},
+    errors::{BuyingError, TipError},
+};
+
+/*
+    Clock In / Clock Out Instructions
+
+    Functionality:
+    - Employees record their own shifts on-chain, in a Shift account created on their first clock in
+    - Clocking out adds the minutes worked (capped at Shift::MAX_SHIFT_MINUTES) to the unpaid minutes,
+      which weigh ByHours tip distributions and are reset once tallied and paid out
+    - Keeps TipPool.on_shift up to date so every on-shift employee must share the next distribution
+
+    Security checks:
+    - Ensures the signer is an employee of the restaurant
+    - The restaurant must have a TipPool
+    - An employee can't clock in twice or clock out without clocking in
+*/
+
+#[derive(Accounts)]
+pub struct ClockShift<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Account<'info, Employee>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = Shift::INIT_SPACE,
+        seeds = [b"shift", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub shift: Account<'info, Shift>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump = tip_pool.bump,
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClockShift<'info> {
+    /// Opens a shift, counting the employee as on shift if they weren't already
+    pub fn clock_in(&mut self, bump: u8) -> Result<()> {
+        require!(self.shift.clocked_in_at.is_none(), TipError::AlreadyClockedIn);
+
+        if !self.shift.is_on_shift() {
+            self.tip_pool.on_shift = self.tip_pool.on_shift.checked_add(1).ok_or(BuyingError::Overflow)?;
+        }
+
+        self.shift.restaurant = self.restaurant.key();
+        self.shift.wallet = self.signer.key();
+        self.shift.clocked_in_at = Some(Clock::get()?.unix_timestamp);
+        self.shift.bump = bump;
+
+        self.emit_shift_clocked(true)
+    }
+
+    /// Closes the open shift, crediting the minutes worked
+    pub fn clock_out(&mut self) -> Result<()> {
+        require!(self.shift.clocked_in_at.is_some(), TipError::NotClockedIn);
+
+        let minutes = self.shift.open_minutes(Clock::get()?.unix_timestamp);
+        self.shift.unpaid_minutes = self.shift.unpaid_minutes.checked_add(minutes).ok_or(BuyingError::Overflow)?;
+        self.shift.clocked_in_at = None;
+
+        // A shift shorter than a minute has nothing to share
+        if !self.shift.is_on_shift() {
+            self.tip_pool.on_shift = self.tip_pool.on_shift.saturating_sub(1);
+        }
+
+        self.emit_shift_clocked(false)
+    }
+
+    fn emit_shift_clocked(&self, clocked_in: bool) -> Result<()> {
+        emit!(ShiftClocked {
+            wallet: self.signer.key(),
+            clocked_in,
+            unpaid_minutes: self.shift.unpaid_minutes,
+            at: Clock::get()?.unix_timestamp,
+            restaurant: self.restaurant.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Handler for clocking in
+pub fn clock_in_handler(ctx: Context<ClockShift>) -> Result<()> {
+    ctx.accounts.clock_in(ctx.bumps.shift)
+}
+
+/// Handler for clocking out
+pub fn clock_out_handler(ctx: Context<ClockShift>) -> Result<()> {
+    ctx.accounts.clock_out()
+}
+
+// Event emitted when an employee clocks in or out
+#[event]
+pub struct ShiftClocked {
+    pub wallet: Pubkey,
+    pub clocked_in: bool,
+    pub unpaid_minutes: u64,
+    pub at: i64,
+    pub restaurant: Pubkey,
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Restaurant, Employee, EmployeeType, TipPool, TipDistribution},
+    errors::{BuyingError, TipError},
+    utils::load_tip_shift,
+};
+
+/*
+    Tally Tips Instruction
+
+    Functionality:
+    - First step of a distribution round: records the share weight of a batch of on-shift employees,
+      so a round can cover more staff than fit in a single transaction
+    - Each employee's weight follows the pool's distribution: equal, the TipPool role weight of their
+      EmployeeType, or the minutes worked since their last payout as recorded on-chain
+    - Banks the minutes of open shifts, which keep running from now, so work done while the round is
+      paid out counts toward the next one
+    - Shifts of removed employees are tallied with no weight, so the payout resets them
+    - Adds each weight to the round's total, which restaurant_distribute_tips shares the pool by
+
+    Security checks:
+    - Ensures the signer is the restaurant admin or a Manager (or above) of the restaurant
+    - Employee and Shift accounts must be the restaurant's PDAs for the same wallet
+    - A Shift can only be tallied once per round, and only before the round's first payout
+
+    Remaining accounts:
+    - One [employee, shift (writable)] pair per on-shift employee of the batch; for a removed employee
+      the employee account is its closed PDA
+*/
+
+#[derive(Accounts)]
+pub struct TallyTips<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub restaurant: Account<'info, Restaurant>,
+
+    #[account(
+        seeds = [b"employee", restaurant.key().as_ref(), signer.key().as_ref()],
+        bump = employee.bump,
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+
+    #[account(
+        mut,
+        seeds = [b"tip_pool", restaurant.key().as_ref()],
+        bump = tip_pool.bump,
+    )]
+    pub tip_pool: Account<'info, TipPool>,
+}
+
+impl<'info> TallyTips<'info> {
+    pub fn tally_tips(&mut self, remaining_accounts: &'info [AccountInfo<'info>], now: i64) -> Result<()> {
+        // Only the owner or a Manager and above can run a distribution
+        let is_manager = self.employee
+            .as_ref()
+            .is_some_and(|employee| employee.employee_type >= EmployeeType::Manager);
+        require!(self.signer.key() == self.restaurant.owner || is_manager, TipError::Unauthorized);
+
+        // Weights added after the first payout would change the shares already paid
+        require!(self.tip_pool.round_paid == 0, TipError::DistributionInProgress);
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 2 == 0,
+            TipError::InvalidEmployeeAccounts
+        );
+
+        let restaurant_key = self.restaurant.key();
+        for pair in remaining_accounts.chunks(2) {
+            let (employee, mut shift) = load_tip_shift(&restaurant_key, &pair[0], &pair[1])?;
+            require!(shift.is_on_shift(), TipError::NotOnShift);
+            require!(shift.tip_round != self.tip_pool.round, TipError::AlreadyTallied);
+
+            let minutes_worked = shift.worked_minutes(now);
+            let weight = match &employee {
+                None => 0,
+                Some(employee) => match self.tip_pool.distribution {
+                    TipDistribution::Equal => 1,
+                    TipDistribution::ByRole => self.tip_pool.role_weight(&employee.employee_type) as u64,
+                    TipDistribution::ByHours => minutes_worked,
+                },
+            };
+
+            shift.unpaid_minutes = minutes_worked;
+            if shift.clocked_in_at.is_some() {
+                shift.clocked_in_at = Some(now);
+            }
+            shift.tip_round = self.tip_pool.round;
+            shift.tip_minutes = minutes_worked;
+            shift.tip_weight = weight;
+            shift.tip_paid = false;
+            // Written back straight away so a Shift passed twice is caught as already tallied
+            shift.exit(&crate::ID)?;
+
+            self.tip_pool.round_weight = self.tip_pool.round_weight.checked_add(weight).ok_or(BuyingError::Overflow)?;
+            self.tip_pool.round_tallied = self.tip_pool.round_tallied.checked_add(1).ok_or(BuyingError::Overflow)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, TallyTips<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.tally_tips(ctx.remaining_accounts, now)
+}
@@ -1,9 +1,13 @@
+// The SBF toolchain's rustc predates usize::is_multiple_of and Option::is_none_or
+#![allow(clippy::manual_is_multiple_of, clippy::unnecessary_map_or)]
+
 use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 pub use constants::*;
 pub use instructions::*;
@@ -38,6 +42,21 @@ pub mod hestia_protocol {
         instructions::remove_admin::handler(ctx)
     }
 
+    /// Add an off-chain payment-attestation signer (can only be called by the protocol admin)
+    pub fn protocol_add_payment_signer(ctx: Context<ManagePaymentSigner>, args: AddPaymentSignerArgs) -> Result<()> {
+        instructions::payment_signers::add_payment_signer_handler(ctx, args)
+    }
+
+    /// Rotate a payment signer to a new key (can only be called by the protocol admin)
+    pub fn protocol_rotate_payment_signer(ctx: Context<ManagePaymentSigner>, args: RotatePaymentSignerArgs) -> Result<()> {
+        instructions::payment_signers::rotate_payment_signer_handler(ctx, args)
+    }
+
+    /// Revoke a payment signer (can only be called by the protocol admin)
+    pub fn protocol_revoke_payment_signer(ctx: Context<ManagePaymentSigner>, args: RevokePaymentSignerArgs) -> Result<()> {
+        instructions::payment_signers::revoke_payment_signer_handler(ctx, args)
+    }
+
     /// Restaurant Management Functions
     /// These functions handle restaurant-specific operations
 
@@ -46,6 +65,26 @@ pub mod hestia_protocol {
         instructions::initialize_restaurant::handler(ctx, args)
     }
 
+    /// Set the restaurant's tax rates and service charge
+    pub fn restaurant_update_tax_config(ctx: Context<UpdateTaxConfig>, args: UpdateTaxConfigArgs) -> Result<()> {
+        instructions::update_tax_config::handler(ctx, args)
+    }
+
+    /// Set the restaurant's UTC offset, used by menu price schedules
+    pub fn restaurant_update_utc_offset(ctx: Context<UpdateUtcOffset>, utc_offset_minutes: i16) -> Result<()> {
+        instructions::update_utc_offset::handler(ctx, utc_offset_minutes)
+    }
+
+    /// Withdraw native SOL revenue held by the restaurant account
+    pub fn restaurant_withdraw_revenue(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
+        instructions::withdraw_revenue::handler(ctx, amount)
+    }
+
+    /// Migrate a restaurant created before the reward rate, tax and UTC offset settings to the current layout
+    pub fn restaurant_migrate(ctx: Context<MigrateRestaurant>) -> Result<()> {
+        instructions::migrate_restaurant::handler(ctx)
+    }
+
     /// Employee Management
 
     /// Add a restaurant employee
@@ -75,6 +114,68 @@ pub mod hestia_protocol {
         instructions::remove_inventory::handler(ctx, args)
     }
 
+    /// Migrate an inventory item created before reorder levels and lots to the current layout
+    pub fn restaurant_migrate_inventory_item(ctx: Context<MigrateInventoryItem>, args: MigrateInventoryItemArgs) -> Result<()> {
+        instructions::migrate_inventory_item::handler(ctx, args)
+    }
+
+    /// Deactivate the menu items that depend on a depleted inventory item
+    pub fn restaurant_deactivate_dependent_items<'info>(ctx: Context<'_, '_, 'info, 'info, DeactivateDependentItems<'info>>, args: DeactivateDependentItemsArgs) -> Result<()> {
+        instructions::deactivate_dependent_items::handler(ctx, args)
+    }
+
+    /// Receive a batch of an inventory item as a new lot
+    pub fn restaurant_receive_inventory_lot(ctx: Context<ReceiveInventoryLot>, args: ReceiveInventoryLotArgs) -> Result<()> {
+        instructions::receive_inventory_lot::handler(ctx, args)
+    }
+
+    /// Write off the expired lots of an inventory item
+    pub fn restaurant_write_off_expired_lots(ctx: Context<WriteOffExpiredLots>, args: WriteOffExpiredLotsArgs) -> Result<()> {
+        instructions::write_off_expired_lots::handler(ctx, args)
+    }
+
+    /// Record stock thrown away with a reason, valued at cost
+    pub fn restaurant_record_waste(ctx: Context<RecordWaste>, args: RecordWasteArgs) -> Result<()> {
+        instructions::record_waste::handler(ctx, args)
+    }
+
+    /// Open a physical stock-count session
+    pub fn restaurant_open_stock_count(ctx: Context<OpenStockCount>, args: OpenStockCountArgs) -> Result<()> {
+        instructions::open_stock_count::handler(ctx, args)
+    }
+
+    /// Submit the counted quantity of an inventory item
+    pub fn restaurant_submit_stock_count(ctx: Context<SubmitStockCount>, args: SubmitStockCountArgs) -> Result<()> {
+        instructions::submit_stock_count::handler(ctx, args)
+    }
+
+    /// Apply the counted variances to the stock and finalize the session
+    pub fn restaurant_finalize_stock_count<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeStockCount<'info>>, args: FinalizeStockCountArgs) -> Result<()> {
+        instructions::finalize_stock_count::handler(ctx, args)
+    }
+
+    /// Supplier Management
+
+    /// Add or update a supplier
+    pub fn restaurant_add_supplier(ctx: Context<ManageSupplier>, args: SupplierArgs) -> Result<()> {
+        instructions::add_supplier::handler(ctx, args)
+    }
+
+    /// Send a purchase order to a supplier
+    pub fn restaurant_create_purchase_order<'info>(ctx: Context<'_, '_, 'info, 'info, CreatePurchaseOrder<'info>>, args: CreatePurchaseOrderArgs) -> Result<()> {
+        instructions::create_purchase_order::handler(ctx, args)
+    }
+
+    /// Receive a purchase order delivery, fully or partially
+    pub fn restaurant_receive_purchase_order<'info>(ctx: Context<'_, '_, 'info, 'info, ReceivePurchaseOrder<'info>>, args: ReceivePurchaseOrderArgs) -> Result<()> {
+        instructions::receive_purchase_order::handler(ctx, args)
+    }
+
+    /// Pay a supplier for the goods received on a purchase order
+    pub fn restaurant_pay_purchase_order<'info>(ctx: Context<'_, '_, 'info, 'info, PayPurchaseOrder<'info>>, args: PayPurchaseOrderArgs) -> Result<()> {
+        instructions::pay_purchase_order::handler(ctx, args)
+    }
+
     /// Menu Management
 
     /// Add a menu item
@@ -92,6 +193,66 @@ pub mod hestia_protocol {
         instructions::toggle_menu_item::handler(ctx, args)
     }
 
+    /// Remove a menu item and reclaim its rent
+    pub fn restaurant_remove_menu_item(ctx: Context<RemoveMenuItem>, args: RemoveMenuItemArgs) -> Result<()> {
+        instructions::remove_menu_item::handler(ctx, args)
+    }
+
+    /// Add a menu item to a menu section, creating the section if needed
+    pub fn restaurant_add_menu_entry(ctx: Context<ManageMenuSection>, args: AddMenuEntryArgs) -> Result<()> {
+        instructions::menu_sections::add_menu_entry_handler(ctx, args)
+    }
+
+    /// Move a menu entry within its section, or a whole menu section
+    pub fn restaurant_move_menu_entry(ctx: Context<ManageMenuSection>, args: MoveMenuEntryArgs) -> Result<()> {
+        instructions::menu_sections::move_menu_entry_handler(ctx, args)
+    }
+
+    /// Remove a menu item from a menu section
+    pub fn restaurant_remove_menu_entry(ctx: Context<ManageMenuSection>, args: RemoveMenuEntryArgs) -> Result<()> {
+        instructions::menu_sections::remove_menu_entry_handler(ctx, args)
+    }
+
+    /// Migrate a restaurant's Menu created before menu sections to the current layout
+    pub fn restaurant_migrate_menu(ctx: Context<MigrateMenu>) -> Result<()> {
+        instructions::migrate_menu::handler(ctx)
+    }
+
+    /// Create or replace a menu item's price schedule
+    pub fn restaurant_set_price_schedule(ctx: Context<SetPriceSchedule>, args: SetPriceScheduleArgs) -> Result<()> {
+        instructions::set_price_schedule::handler(ctx, args)
+    }
+
+    /// Remove a menu item's price schedule
+    pub fn restaurant_remove_price_schedule(ctx: Context<RemovePriceSchedule>, args: RemovePriceScheduleArgs) -> Result<()> {
+        instructions::remove_price_schedule::handler(ctx, args)
+    }
+
+    /// Attach a modifier group to a menu item
+    pub fn restaurant_add_modifier_group(ctx: Context<AddModifierGroup>, args: AddModifierGroupArgs) -> Result<()> {
+        instructions::add_modifier_group::handler(ctx, args)
+    }
+
+    /// Remove a modifier group from a menu item
+    pub fn restaurant_remove_modifier_group(ctx: Context<RemoveModifierGroup>, args: RemoveModifierGroupArgs) -> Result<()> {
+        instructions::remove_modifier_group::handler(ctx, args)
+    }
+
+    /// Create or replace the component slots of a combo menu item
+    pub fn restaurant_set_combo<'info>(ctx: Context<'_, '_, 'info, 'info, SetCombo<'info>>, args: SetComboArgs) -> Result<()> {
+        instructions::set_combo::handler(ctx, args)
+    }
+
+    /// Remove the component slots of a combo menu item
+    pub fn restaurant_remove_combo<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveCombo<'info>>, args: RemoveComboArgs) -> Result<()> {
+        instructions::remove_combo::handler(ctx, args)
+    }
+
+    /// Move a legacy menu item to its restaurant-scoped address (can only be called by the protocol admin)
+    pub fn protocol_migrate_menu_item(ctx: Context<MigrateMenuItem>, args: MigrateMenuItemArgs) -> Result<()> {
+        instructions::migrate_menu_item::handler(ctx, args)
+    }
+
     /// Reward Management
 
     /// Create a reward account
@@ -109,6 +270,55 @@ pub mod hestia_protocol {
         instructions::remove_reward::handler(ctx)
     }
 
+    /// Migrate a legacy reward voucher to reference its menu item by SKU
+    pub fn restaurant_migrate_reward_voucher(ctx: Context<MigrateRewardVoucher>, args: MigrateRewardVoucherArgs) -> Result<()> {
+        instructions::migrate_reward::handler(ctx, args)
+    }
+
+    /// Update the reward points earned per whole currency token
+    pub fn restaurant_update_reward_rate(ctx: Context<UpdateRewardRate>, reward_points_per_token: u64) -> Result<()> {
+        instructions::update_reward_rate::handler(ctx, reward_points_per_token)
+    }
+
+    /// Promotion Management
+
+    /// Create a promo code
+    pub fn restaurant_create_promotion(ctx: Context<CreatePromotion>, args: CreatePromotionArgs) -> Result<()> {
+        instructions::create_promotion::handler(ctx, args)
+    }
+
+    /// Pause or resume a promo code
+    pub fn restaurant_toggle_promotion(ctx: Context<TogglePromotion>, args: TogglePromotionArgs) -> Result<()> {
+        instructions::toggle_promotion::handler(ctx, args)
+    }
+
+    /// Tip Management
+
+    /// Create or reconfigure the restaurant's tip pool
+    pub fn restaurant_configure_tip_pool(ctx: Context<ConfigureTipPool>, args: ConfigureTipPoolArgs) -> Result<()> {
+        instructions::configure_tip_pool::handler(ctx, args)
+    }
+
+    /// Record the share weights of a batch of on-shift employees for the next tip distribution
+    pub fn restaurant_tally_tips<'info>(ctx: Context<'_, '_, 'info, 'info, TallyTips<'info>>) -> Result<()> {
+        instructions::tally_tips::handler(ctx)
+    }
+
+    /// Pay out the tip pool to a batch of tallied employees
+    pub fn restaurant_distribute_tips<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeTips<'info>>) -> Result<()> {
+        instructions::distribute_tips::handler(ctx)
+    }
+
+    /// Clock in for a shift
+    pub fn employee_clock_in(ctx: Context<ClockShift>) -> Result<()> {
+        instructions::shifts::clock_in_handler(ctx)
+    }
+
+    /// Clock out of the current shift
+    pub fn employee_clock_out(ctx: Context<ClockShift>) -> Result<()> {
+        instructions::shifts::clock_out_handler(ctx)
+    }
+
     /// Order Management
 
     /// Update an order
@@ -125,12 +335,22 @@ pub mod hestia_protocol {
     }
 
     /// Add a new order
-    pub fn restaurant_add_order(ctx: Context<AddCustomerOrder>, args: CustomerOrderArgs) -> Result<()> {
+    pub fn restaurant_add_order<'info>(ctx: Context<'_, '_, 'info, 'info, AddCustomerOrder<'info>>, args: CustomerOrderArgs) -> Result<()> {
         instructions::add_order::handler(ctx, args)
     }
 
+    /// Pay one share of a split-bill order
+    pub fn customer_pay_order_share(ctx: Context<PayOrderShare>, args: PayOrderShareArgs) -> Result<()> {
+        instructions::pay_order_share::handler(ctx, args)
+    }
+
     /// Cancel an existing order
-    pub fn restaurant_cancel_order(ctx: Context<CancelCustomerOrder>, args: CancelOrderArgs) -> Result<()> {
+    pub fn restaurant_cancel_order<'info>(ctx: Context<'_, '_, 'info, 'info, CancelCustomerOrder<'info>>, args: CancelOrderArgs) -> Result<()> {
         instructions::cancel_order::handler(ctx, args)
     }
+
+    /// Refund part or all of an order's payment
+    pub fn restaurant_refund_order(ctx: Context<RefundCustomerOrder>, args: RefundOrderArgs) -> Result<()> {
+        instructions::refund_order::handler(ctx, args)
+    }
 }
\ No newline at end of file
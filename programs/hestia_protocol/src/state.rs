@@ -22,6 +22,40 @@ impl Space for Manager {
     const INIT_SPACE: usize = 8 + 1;
 }
 
+#[account]
+pub struct PaymentConfig {
+    pub signers: Vec<PaymentSigner>,
+    pub bump: u8,
+}
+
+impl Space for PaymentConfig {
+    const INIT_SPACE: usize = 8 + 4 + PaymentSigner::INIT_SPACE * PaymentConfig::MAX_SIGNERS + 1;
+}
+
+impl PaymentConfig {
+    pub const MAX_SIGNERS: usize = 8;
+
+    /// Returns true if `key` is an active payment-attestation signer
+    pub fn is_active_signer(&self, key: &Pubkey) -> bool {
+        self.signers.iter().any(|signer| signer.key == *key && signer.active)
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct PaymentSigner {
+    pub key: Pubkey,
+    pub label: String,
+    pub active: bool,
+}
+
+impl Space for PaymentSigner {
+    const INIT_SPACE: usize = 32 + 4 + PaymentSigner::MAX_LABEL_LEN + 1;
+}
+
+impl PaymentSigner {
+    pub const MAX_LABEL_LEN: usize = 32;
+}
+
 #[account]
 pub struct AdminProfile {
     pub username: String,
@@ -44,11 +78,16 @@ pub struct Restaurant {
     pub currency: Pubkey,
     pub url: String,
     pub customer_count: u64,
+    pub reward_points_per_token: u64, // points earned per whole currency token spent
+    pub tax_bps: u16,
+    pub category_tax_bps: [Option<u16>; 7], // per MenuCategoryType override of tax_bps
+    pub service_charge_bps: u16,
+    pub utc_offset_minutes: i16, // local time used by menu price schedules
     pub bump: u8,
 }
 
 impl Space for Restaurant {
-    const INIT_SPACE: usize = 8 + 4 + 4 + 32 + 32 + 4 + 32 + 32 + 4 + 8 + 1;
+    const INIT_SPACE: usize = 8 + 4 + 4 + 32 + 32 + 4 + 32 + 32 + 4 + 8 + 8 + 2 + 3 * 7 + 2 + 2 + 1;
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
@@ -72,7 +111,7 @@ impl Space for Employee {
     const INIT_SPACE: usize = 8 + 32 + 32 + 4 + 4 + 1;
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq)]
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq, PartialOrd)]
 pub enum EmployeeType {
     TeamMember,
     TeamLeader,
@@ -87,15 +126,71 @@ pub struct InventoryItem {
     pub sku: String,
     pub category: InventoryCategoryType,
     pub name: String,
-    pub price: u64,
+    pub price: u64, // in currency base units
     pub stock: u64,
     pub last_order: i64,
+    pub reorder_threshold: u64, // LowStock is emitted when stock drops below this
+    pub par_level: u64, // stock to reorder up to
+    pub auto_deactivate: bool, // dependent menu items can be deactivated once this runs out
+    pub lots: Vec<InventoryLot>, // received batches, oldest first; stock not covered by a lot is untracked
     pub initialized: bool,
     pub bump: u8,
 }
 
 impl Space for InventoryItem {
-    const INIT_SPACE: usize = 8 + 8 + 32 + 4 + 8 + 8 + 8 + 1;
+    const INIT_SPACE: usize = 8 + 8 + 32 + 4 + 8 + 8 + 8 + 8 + 8 + 1 + 4 + InventoryLot::INIT_SPACE * Self::MAX_LOTS + 1 + 1;
+}
+
+impl InventoryItem {
+    pub const MAX_LOTS: usize = 8;
+
+    /// Stock covered by a lot
+    pub fn lot_stock(&self) -> u64 {
+        self.lots.iter().map(|lot| lot.quantity).sum()
+    }
+
+    /// Removes `quantity` from stock, consuming lots first-in-first-out before any untracked stock.
+    /// Returns the cost of the consumed units (untracked stock is valued at `price`), None when short on stock
+    pub fn consume(&mut self, quantity: u64) -> Option<u64> {
+        self.stock = self.stock.checked_sub(quantity)?;
+
+        let mut remaining = quantity;
+        let mut cost = 0u64;
+        for lot in self.lots.iter_mut() {
+            let taken = remaining.min(lot.quantity);
+            lot.quantity -= taken;
+            remaining -= taken;
+            cost = cost.saturating_add(taken.saturating_mul(lot.unit_cost));
+            if remaining == 0 {
+                break;
+            }
+        }
+        self.lots.retain(|lot| lot.quantity > 0);
+
+        Some(cost.saturating_add(remaining.saturating_mul(self.price)))
+    }
+
+    /// Removes every lot expired at `now` from the lots and the stock, returning the (quantity, cost) written off
+    pub fn remove_expired_lots(&mut self, now: i64) -> (u64, u64) {
+        let (expired, fresh): (Vec<InventoryLot>, Vec<InventoryLot>) = self.lots
+            .drain(..)
+            .partition(|lot| lot.expires_at.is_some_and(|expires_at| expires_at <= now));
+        self.lots = fresh;
+
+        let quantity: u64 = expired.iter().map(|lot| lot.quantity).sum();
+        let cost = expired.iter().fold(0u64, |cost, lot| cost.saturating_add(lot.quantity.saturating_mul(lot.unit_cost)));
+        self.stock = self.stock.saturating_sub(quantity);
+
+        (quantity, cost)
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct InventoryLot {
+    pub quantity: u64, // left in this lot
+    pub unit_cost: u64, // in currency base units
+    pub received_at: i64,
+    pub expires_at: Option<i64>, // None for goods that don't expire
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
@@ -111,34 +206,328 @@ pub enum InventoryCategoryType {
     Other,
 }
 
+#[account]
+pub struct WasteLog {
+    pub restaurant: Pubkey,
+    pub total_cost: u64, // in currency base units
+    pub cost_by_reason: [u64; 5], // indexed by WasteReason
+    pub entries_by_reason: [u64; 5], // indexed by WasteReason
+    pub bump: u8,
+}
+
+impl Space for WasteLog {
+    const INIT_SPACE: usize = 8 + 32 + 8 + 8 * 5 + 8 * 5 + 1;
+}
+
+impl WasteLog {
+    /// Adds a waste entry to the running totals, None on overflow
+    pub fn record(&mut self, reason: &WasteReason, cost: u64) -> Option<()> {
+        let index = reason.clone() as usize;
+        self.total_cost = self.total_cost.checked_add(cost)?;
+        self.cost_by_reason[index] = self.cost_by_reason[index].checked_add(cost)?;
+        self.entries_by_reason[index] = self.entries_by_reason[index].checked_add(1)?;
+        Some(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq, InitSpace)]
+pub enum WasteReason {
+    Spoiled,
+    Dropped,
+    Comped,
+    Theft,
+    Expired, // expired lots written off
+}
+
+#[account]
+pub struct StockCount {
+    pub restaurant: Pubkey,
+    pub id: u64,
+    pub status: StockCountStatus,
+    pub records: u16, // items counted in this session
+    pub applied: u16, // records whose variance has been applied to the stock
+    pub opened_by: Pubkey,
+    pub opened_at: i64,
+    pub finalized_by: Option<Pubkey>,
+    pub finalized_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl Space for StockCount {
+    const INIT_SPACE: usize = 8 + 32 + 8 + 1 + 2 + 2 + 32 + 8 + 33 + 9 + 1;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq, InitSpace)]
+pub enum StockCountStatus {
+    Open, // accepting counts
+    Finalizing, // adjustments being applied, counts are locked
+    Finalized,
+}
+
+#[account]
+pub struct StockCountRecord {
+    pub stock_count: Pubkey,
+    pub inventory_item: Pubkey,
+    pub counted: u64,
+    pub expected: u64, // InventoryItem.stock when the count was submitted
+    pub variance: i64, // counted - expected
+    pub counted_by: Pubkey,
+    pub counted_at: i64,
+    pub applied: bool,
+    pub bump: u8,
+}
+
+impl Space for StockCountRecord {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct Supplier {
+    pub restaurant: Pubkey,
+    pub id: u64,
+    pub name: String,
+    pub wallet: Pubkey, // receives PurchaseOrder payments in Restaurant.currency
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl Space for Supplier {
+    const INIT_SPACE: usize = 8 + 32 + 8 + 4 + 32 + 1 + 1;
+}
+
+impl Supplier {
+    pub const MAX_NAME_LEN: usize = 32;
+}
+
+#[account]
+pub struct PurchaseOrder {
+    pub restaurant: Pubkey,
+    pub supplier: Pubkey,
+    pub id: u64,
+    pub lines: Vec<PurchaseOrderLine>,
+    pub total: u64, // agreed cost of every line, in currency base units
+    pub paid: u64, // never more than the cost of what was received
+    pub status: PurchaseOrderStatus,
+    pub created_at: i64,
+    pub received_at: Option<i64>, // last delivery
+    pub bump: u8,
+}
+
+impl Space for PurchaseOrder {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 4 + 8 + 8 + 1 + 8 + 1 + 8 + 1;
+}
+
+impl PurchaseOrder {
+    pub const MAX_LINES: usize = 16;
+
+    /// Cost of the quantities received so far
+    pub fn received_value(&self) -> Option<u64> {
+        self.lines.iter().try_fold(0u64, |sum, line| {
+            line.received.checked_mul(line.unit_cost).and_then(|cost| sum.checked_add(cost))
+        })
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct PurchaseOrderLine {
+    pub inventory_item: Pubkey,
+    pub quantity: u64,
+    pub unit_cost: u64, // in currency base units
+    pub received: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq, InitSpace)]
+pub enum PurchaseOrderStatus {
+    Open,
+    PartiallyReceived,
+    Received,
+}
+
 /// Menu-related Structures
 
 #[account]
 pub struct Menu {
     pub initialized: bool,
     pub bump: u8,
+    pub sections: Vec<MenuSection>, // in display order
 }
 
 impl Space for Menu {
-    const INIT_SPACE: usize = 8 + 4 + 1;
+    const INIT_SPACE: usize = 8 + 1 + 1 + 4;
+}
+
+impl Menu {
+    pub const MAX_SECTIONS: usize = 16;
+
+    /// Account size needed to hold the current sections
+    pub fn space(&self) -> usize {
+        Menu::INIT_SPACE + self.sections.iter().map(|section| section.space()).sum::<usize>()
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct MenuSection {
+    pub name: String,
+    pub items: Vec<Pubkey>, // MenuItem keys, in display order
+}
+
+impl Space for MenuSection {
+    const INIT_SPACE: usize = 4 + 4;
+}
+
+impl MenuSection {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_ITEMS: usize = 64;
+
+    pub fn space(&self) -> usize {
+        MenuSection::INIT_SPACE + self.name.len() + self.items.len() * 32
+    }
 }
 
 #[account]
 pub struct MenuItem {
+    pub restaurant: Pubkey,
     pub sku: String,
     pub category: MenuCategoryType,
     pub name: String,
-    pub price: u64,
+    pub price: u64, // in currency base units
     pub description: String,
     pub active: bool,
+    pub price_schedule: Option<Pubkey>,
+    pub modifier_groups: Vec<Pubkey>,
+    pub combo: Option<Pubkey>, // component slots of a MenuCategoryType::Combo item
+    pub active_vouchers: u16, // RewardVouchers referencing this item that are not removed or completed
+    pub combo_references: u16, // Combos using this item as a slot component or substitute
     pub bump: u8,
 }
 
 impl Space for MenuItem {
-    const INIT_SPACE: usize = 8 + 4 + MenuCategoryType::INIT_SPACE + 4 + 8 + 4 + 4 + 1;
+    const INIT_SPACE: usize = 8 + 32 + 4 + MenuCategoryType::INIT_SPACE + 4 + 8 + 4 + 4 + 1 + 32 + 4 + 32 * MenuItem::MAX_MODIFIER_GROUPS + 1 + 1 + 32 + 2 + 2;
+}
+
+impl MenuItem {
+    pub const MAX_MODIFIER_GROUPS: usize = 4;
+}
+
+#[account]
+pub struct ModifierGroup {
+    pub menu_item: Pubkey,
+    pub name: String,
+    pub min_selections: u8,
+    pub max_selections: u8,
+    pub options: Vec<ModifierOption>,
+    pub bump: u8,
+}
+
+impl Space for ModifierGroup {
+    const INIT_SPACE: usize = 8 + 32 + 4 + 1 + 1 + 4 + 1;
+}
+
+impl ModifierGroup {
+    pub const MAX_OPTIONS: usize = 16;
+    pub const MAX_NAME_LEN: usize = 32;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct ModifierOption {
+    pub name: String,
+    pub price_delta: i64, // in currency base units, added to the unit price
+    pub ingredients: Vec<(Pubkey, u64)>, // (InventoryItem pubkey, quantity per unit)
+}
+
+impl Space for ModifierOption {
+    const INIT_SPACE: usize = 4 + 8 + 4;
+}
+
+impl ModifierOption {
+    /// Space taken by this option inside the ModifierGroup account
+    pub fn space(&self) -> usize {
+        ModifierOption::INIT_SPACE + self.name.len() + self.ingredients.len() * (32 + 8)
+    }
+}
+
+/// A selected option, by index into MenuItem.modifier_groups and ModifierGroup.options
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq)]
+pub struct ModifierSelection {
+    pub group: u8,
+    pub option: u8,
+}
+
+#[account]
+pub struct Combo {
+    pub menu_item: Pubkey, // the bundle MenuItem, sold at its own price
+    pub slots: Vec<ComboSlot>,
+    pub bump: u8,
+}
+
+impl Space for Combo {
+    const INIT_SPACE: usize = 8 + 32 + 4 + ComboSlot::INIT_SPACE * Combo::MAX_SLOTS + 1;
+}
+
+impl Combo {
+    pub const MAX_SLOTS: usize = 8;
+    pub const MAX_SUBSTITUTES: usize = 4;
+
+    /// Every distinct menu item referenced by `slots`, as a component or a substitute
+    pub fn referenced_items(slots: &[ComboSlot]) -> Vec<Pubkey> {
+        let mut items: Vec<Pubkey> = Vec::new();
+        for key in slots.iter().flat_map(|slot| std::iter::once(&slot.menu_item).chain(slot.substitutes.iter())) {
+            if !items.contains(key) {
+                items.push(*key);
+            }
+        }
+        items
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct ComboSlot {
+    pub menu_item: Pubkey, // default component
+    pub quantity: u16, // units of the component per combo
+    pub substitutes: Vec<Pubkey>, // components the customer may pick instead
+}
+
+impl Space for ComboSlot {
+    const INIT_SPACE: usize = 32 + 2 + 4 + 32 * Combo::MAX_SUBSTITUTES;
+}
+
+/// A substituted combo slot, by index into Combo.slots and ComboSlot.substitutes
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, PartialEq)]
+pub struct ComboSubstitution {
+    pub slot: u8,
+    pub substitute: u8,
+}
+
+#[account]
+pub struct PriceSchedule {
+    pub menu_item: Pubkey,
+    pub windows: Vec<PriceWindow>, // the first window matching the local time wins
+    pub bump: u8,
+}
+
+impl Space for PriceSchedule {
+    const INIT_SPACE: usize = 8 + 32 + 4 + PriceWindow::INIT_SPACE * PriceSchedule::MAX_WINDOWS + 1;
+}
+
+impl PriceSchedule {
+    pub const MAX_WINDOWS: usize = 8;
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct PriceWindow {
+    pub days: u8, // bitmask of local weekdays, bit 0 = Sunday
+    pub start_minute: u16, // minutes since local midnight, inclusive
+    pub end_minute: u16, // exclusive
+    pub adjustment: PriceAdjustment,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub enum PriceAdjustment {
+    Price { amount: u64 }, // in currency base units
+    PercentOff { bps: u16 },
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq)]
 pub enum MenuCategoryType {
     Combo,
     Side,
@@ -177,12 +566,39 @@ impl Space for Customer {
     const INIT_SPACE: usize = 8 + 4 + 4 + 32 + 8 + 1;
 }
 
+impl Customer {
+    /// A new customer record, member_since is backdated like AdminProfile.creation_time
+    pub fn new(name: Option<String>, restaurant: Pubkey, now: i64, total_orders: u64, reward_points: u64, bump: u8) -> Self {
+        Self {
+            initialized: true,
+            name: name.unwrap_or("no name".to_string()),
+            restaurant,
+            member_since: now - 20 * 60 * 60,
+            total_orders,
+            reward_points,
+            bump,
+        }
+    }
+}
+
 #[account]
 pub struct CustomerOrder {
     pub order_id: u64,
     pub customer: Pubkey,
-    pub items: Vec<u64>,
-    pub total: u64,
+    pub lines: Vec<OrderLine>,
+    pub subtotal: u64, // in currency base units, as are discount, tax, service_charge, total, paid and refunded
+    pub discount: u64, // redeemed reward item plus promotion discount
+    pub promotion: Option<Pubkey>,
+    pub tax: u64,
+    pub service_charge: u64,
+    pub total: u64, // subtotal - discount + tax + service_charge
+    pub paid: u64,
+    pub refunded: u64,
+    pub tip: u64,
+    pub reward_points: u64,
+    pub shares: Vec<BillShare>, // empty unless the bill is split between several payers
+    pub stock_taken: Vec<StockTaken>, // InventoryItem stock depleted by the order, restored as is on cancel
+    pub escrow_expires_at: Option<i64>, // Some while the payment is held in the order escrow
     pub status: StatusType,
     pub created_at: i64,
     pub updated_at: Option<i64>,
@@ -190,7 +606,78 @@ pub struct CustomerOrder {
 }
 
 impl Space for CustomerOrder {
-    const INIT_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 1;
+    const INIT_SPACE: usize = 8 + 8 + 32 + 4 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 1 + 8 + 1 + 8 + 1 + 8 + 1;
+}
+
+impl CustomerOrder {
+    pub const MAX_SHARES: usize = 12;
+
+    /// True once every share of a split bill has been paid (always true for single payer orders)
+    pub fn is_settled(&self) -> bool {
+        self.shares.iter().all(|share| share.payer.is_some())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct BillShare {
+    pub amount: u64, // in currency base units
+    pub payer: Option<Pubkey>,
+    pub refunded: u64,
+    pub reward_points: u64,
+    pub paid_at: Option<i64>,
+}
+
+impl Space for BillShare {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 8 + 1 + 8;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct StockTaken {
+    pub inventory_item: Pubkey,
+    pub quantity: u64,
+}
+
+impl Space for StockTaken {
+    const INIT_SPACE: usize = 32 + 8;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct OrderLine {
+    pub menu_item: Pubkey,
+    pub quantity: u16,
+    pub unit_price: u64, // in currency base units, including modifier price deltas
+    pub modifiers: Vec<ModifierSelection>,
+    pub substitutions: Vec<ComboSubstitution>,
+    pub note: Option<String>,
+}
+
+impl Space for OrderLine {
+    const INIT_SPACE: usize = 32 + 2 + 8 + 4 + 4 + 1;
+}
+
+/// Off-chain payment attestation, borsh-serialized and signed by the signing authority
+/// through an Ed25519 program instruction placed right before the order instruction
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct StripePaymentMessage {
+    pub order_id: u64,
+    pub restaurant: Pubkey,
+    pub customer: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub expires_at: i64,
+}
+
+#[account]
+pub struct PaymentReceipt {
+    pub order: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub consumed_at: i64,
+    pub bump: u8,
+}
+
+impl Space for PaymentReceipt {
+    const INIT_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1;
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq)]
@@ -199,6 +686,173 @@ pub enum StatusType {
     Completed,
     Finalized,
     Cancelled,
+    Accepted,
+    Preparing,
+    Ready,
+    Refunded,
+}
+
+impl StatusType {
+    /// Returns the minimum EmployeeType allowed to move an order from `self` to `next`,
+    /// or None if the transition is not allowed
+    pub fn transition_role(&self, next: &StatusType) -> Option<EmployeeType> {
+        match (self, next) {
+            (Self::Pending, Self::Accepted) => Some(EmployeeType::TeamMember),
+            (Self::Accepted, Self::Preparing) => Some(EmployeeType::TeamMember),
+            (Self::Preparing, Self::Ready) => Some(EmployeeType::TeamMember),
+            (Self::Ready, Self::Completed) => Some(EmployeeType::TeamMember),
+            (Self::Completed, Self::Finalized) => Some(EmployeeType::TeamLeader),
+            (Self::Pending | Self::Accepted, Self::Cancelled) => Some(EmployeeType::TeamLeader),
+            (Self::Preparing | Self::Ready, Self::Cancelled) => Some(EmployeeType::Manager),
+            (Self::Completed | Self::Finalized, Self::Refunded) => Some(EmployeeType::Manager),
+            _ => None,
+        }
+    }
+}
+
+#[account]
+pub struct TipPool {
+    pub restaurant: Pubkey,
+    pub distribution: TipDistribution,
+    pub role_weights: [u16; 4], // indexed by EmployeeType
+    pub balance: u64, // undistributed tips, in currency base units
+    pub total_collected: u64,
+    pub total_distributed: u64,
+    pub on_shift: u16, // Shift accounts clocked in, holding unpaid minutes or awaiting a payout, every one shares the next distribution
+    pub round: u64, // current distribution round, starting at 1
+    pub round_weight: u64, // share weight tallied in the round
+    pub round_pool: u64, // amount shared by the round, fixed by its first payout
+    pub round_tallied: u16, // Shift accounts tallied in the round
+    pub round_paid: u16, // Shift accounts paid in the round
+    pub bump: u8,
+}
+
+impl Space for TipPool {
+    const INIT_SPACE: usize = 8 + 32 + TipDistribution::INIT_SPACE + 2 * 4 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 2 + 2 + 1;
+}
+
+impl TipPool {
+    /// Share weight of an employee under the ByRole distribution
+    pub fn role_weight(&self, employee_type: &EmployeeType) -> u16 {
+        self.role_weights[employee_type.clone() as usize]
+    }
+
+    /// Tallying or paying out a round has started
+    pub fn is_distributing(&self) -> bool {
+        self.round_tallied > 0
+    }
+
+    /// Moves on to the next round once every tallied Shift has been paid
+    pub fn close_round(&mut self) {
+        self.round = self.round.saturating_add(1);
+        self.round_weight = 0;
+        self.round_pool = 0;
+        self.round_tallied = 0;
+        self.round_paid = 0;
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq)]
+pub enum TipDistribution {
+    Equal,
+    ByRole,
+    ByHours,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct TipPayout {
+    pub employee: Pubkey,
+    pub minutes_worked: u64, // recorded on-chain by the employee's Shift
+    pub weight: u64,
+    pub amount: u64,
+}
+
+#[account]
+pub struct Shift {
+    pub restaurant: Pubkey,
+    pub wallet: Pubkey, // the employee's wallet
+    pub clocked_in_at: Option<i64>,
+    pub unpaid_minutes: u64, // worked since the employee's last tip payout
+    pub tip_round: u64, // last TipPool round the shift was tallied in
+    pub tip_minutes: u64, // minutes tallied for tip_round
+    pub tip_weight: u64, // share weight tallied for tip_round
+    pub tip_paid: bool, // tip_round's share has been paid out
+    pub bump: u8,
+}
+
+impl Space for Shift {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 9 + 8 + 8 + 8 + 8 + 1 + 1;
+}
+
+impl Shift {
+    // Shifts left open longer than this are only credited up to the cap
+    pub const MAX_SHIFT_MINUTES: u64 = 16 * 60;
+
+    /// Clocked in, worked since the last tip payout, or tallied and awaiting its payout
+    pub fn is_on_shift(&self) -> bool {
+        self.clocked_in_at.is_some() || self.unpaid_minutes > 0 || self.awaiting_payout()
+    }
+
+    /// Tallied in a round that hasn't paid it yet
+    pub fn awaiting_payout(&self) -> bool {
+        self.tip_round > 0 && !self.tip_paid
+    }
+
+    /// Minutes of the shift open at `now`, capped at MAX_SHIFT_MINUTES
+    pub fn open_minutes(&self, now: i64) -> u64 {
+        self.clocked_in_at
+            .map(|clocked_in_at| (now.saturating_sub(clocked_in_at).max(0) as u64 / 60).min(Self::MAX_SHIFT_MINUTES))
+            .unwrap_or(0)
+    }
+
+    /// Unpaid minutes including the shift open at `now`
+    pub fn worked_minutes(&self, now: i64) -> u64 {
+        self.unpaid_minutes.saturating_add(self.open_minutes(now))
+    }
+}
+
+/// Promotion-related Structures
+
+#[account]
+pub struct Promotion {
+    pub restaurant: Pubkey,
+    pub code: String,
+    pub rule: PromotionRule,
+    pub category: Option<MenuCategoryType>, // only items of this category are discounted
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub max_redemptions: Option<u32>,
+    pub per_customer_limit: Option<u16>,
+    pub redemptions: u32,
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl Space for Promotion {
+    const INIT_SPACE: usize = 8 + 32 + 4 + Promotion::MAX_CODE_LEN + PromotionRule::INIT_SPACE + 1 + MenuCategoryType::INIT_SPACE + 8 + 8 + 1 + 4 + 1 + 2 + 4 + 1 + 1;
+}
+
+impl Promotion {
+    pub const MAX_CODE_LEN: usize = 16;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace, PartialEq)]
+pub enum PromotionRule {
+    PercentOff { bps: u16 },
+    FixedOff { amount: u64 }, // in currency base units
+    BuyXGetY { buy: u16, get: u16 }, // the cheapest `get` of every `buy + get` eligible units are free
+}
+
+#[account]
+pub struct PromotionRedemption {
+    pub promotion: Pubkey,
+    pub customer: Pubkey,
+    pub count: u16,
+    pub bump: u8,
+}
+
+impl Space for PromotionRedemption {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 2 + 1;
 }
 
 /// Reward-related Structures
@@ -219,7 +873,7 @@ impl Space for Reward {
 #[account]
 pub struct RewardVoucher {
     pub id: u64,
-    pub item_sku: u64,
+    pub item_sku: String, // SKU of the restaurant's MenuItem
     pub reward: Pubkey,
     pub restaurant: Pubkey,
     pub category: MenuCategoryType,
@@ -231,7 +885,7 @@ pub struct RewardVoucher {
 }
 
 impl Space for RewardVoucher {
-    const INIT_SPACE: usize = 8 + 8 + 32 + 32 + MenuCategoryType::INIT_SPACE + 2 + 2 + 8 + 8 + 1;
+    const INIT_SPACE: usize = 8 + 8 + 4 + 32 + 32 + MenuCategoryType::INIT_SPACE + 2 + 2 + 8 + 8 + 1;
 }
 
 #[account]
@@ -252,4 +906,149 @@ impl Space for CompletedRewardVoucher {
 pub struct Attributes {
     pub key: String,
     pub value: String,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lot(quantity: u64, unit_cost: u64, expires_at: Option<i64>) -> InventoryLot {
+        InventoryLot { quantity, unit_cost, received_at: 0, expires_at }
+    }
+
+    fn inventory_item(stock: u64, price: u64, lots: Vec<InventoryLot>) -> InventoryItem {
+        InventoryItem {
+            sku: "sku".to_string(),
+            category: InventoryCategoryType::Food,
+            name: "item".to_string(),
+            price,
+            stock,
+            last_order: 0,
+            reorder_threshold: 0,
+            par_level: 0,
+            auto_deactivate: false,
+            lots,
+            initialized: true,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn consume_uses_lots_first_in_first_out() {
+        let mut item = inventory_item(20, 9, vec![lot(5, 2, None), lot(10, 3, None)]);
+
+        // 5 from the first lot, 2 from the second
+        assert_eq!(item.consume(7), Some(5 * 2 + 2 * 3));
+        assert_eq!(item.stock, 13);
+        assert_eq!(item.lots.len(), 1);
+        assert_eq!(item.lots[0].quantity, 8);
+
+        // 8 from the last lot, 2 untracked units valued at the item price
+        assert_eq!(item.consume(10), Some(8 * 3 + 2 * 9));
+        assert_eq!(item.stock, 3);
+        assert!(item.lots.is_empty());
+    }
+
+    #[test]
+    fn consume_rejects_more_than_the_stock() {
+        let mut item = inventory_item(4, 1, vec![lot(4, 1, None)]);
+
+        assert_eq!(item.consume(5), None);
+        assert_eq!(item.stock, 4);
+        assert_eq!(item.lot_stock(), 4);
+    }
+
+    #[test]
+    fn remove_expired_lots_writes_off_only_expired_lots() {
+        let mut item = inventory_item(16, 1, vec![lot(5, 2, Some(100)), lot(3, 4, None), lot(6, 1, Some(200))]);
+
+        // A lot expiring exactly now is expired
+        assert_eq!(item.remove_expired_lots(100), (5, 10));
+        assert_eq!(item.stock, 11);
+        assert_eq!(item.lots.len(), 2);
+        assert_eq!(item.lots[0].quantity, 3);
+
+        assert_eq!(item.remove_expired_lots(150), (0, 0));
+        assert_eq!(item.remove_expired_lots(250), (6, 6));
+        assert_eq!(item.stock, 5);
+    }
+
+    #[test]
+    fn waste_log_records_totals_by_reason() {
+        let mut log = WasteLog {
+            restaurant: Pubkey::default(),
+            total_cost: 0,
+            cost_by_reason: [0; 5],
+            entries_by_reason: [0; 5],
+            bump: 255,
+        };
+
+        log.record(&WasteReason::Spoiled, 100).unwrap();
+        log.record(&WasteReason::Spoiled, 50).unwrap();
+        log.record(&WasteReason::Expired, 30).unwrap();
+        assert_eq!(log.total_cost, 180);
+        assert_eq!(log.cost_by_reason, [150, 0, 0, 0, 30]);
+        assert_eq!(log.entries_by_reason, [2, 0, 0, 0, 1]);
+
+        // An overflowing entry is rejected
+        assert_eq!(log.record(&WasteReason::Theft, u64::MAX), None);
+    }
+
+    #[test]
+    fn transition_role_allows_only_the_order_lifecycle() {
+        use EmployeeType::*;
+        use StatusType::*;
+
+        let statuses = [Pending, Completed, Finalized, Cancelled, Accepted, Preparing, Ready, Refunded];
+        let allowed = [
+            (Pending, Accepted, TeamMember),
+            (Accepted, Preparing, TeamMember),
+            (Preparing, Ready, TeamMember),
+            (Ready, Completed, TeamMember),
+            (Completed, Finalized, TeamLeader),
+            (Pending, Cancelled, TeamLeader),
+            (Accepted, Cancelled, TeamLeader),
+            (Preparing, Cancelled, Manager),
+            (Ready, Cancelled, Manager),
+            (Completed, Refunded, Manager),
+            (Finalized, Refunded, Manager),
+        ];
+
+        for from in statuses.iter() {
+            for to in statuses.iter() {
+                let expected = allowed
+                    .iter()
+                    .find(|(allowed_from, allowed_to, _)| allowed_from == from && allowed_to == to)
+                    .map(|(_, _, role)| role.clone());
+                assert!(
+                    from.transition_role(to) == expected,
+                    "transition {} -> {}",
+                    from.clone() as u8,
+                    to.clone() as u8
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shift_stays_on_shift_until_its_tallied_share_is_paid() {
+        let mut shift = Shift {
+            restaurant: Pubkey::default(),
+            wallet: Pubkey::default(),
+            clocked_in_at: None,
+            unpaid_minutes: 0,
+            tip_round: 0,
+            tip_minutes: 0,
+            tip_weight: 0,
+            tip_paid: false,
+            bump: 0,
+        };
+        assert!(!shift.is_on_shift());
+
+        // Tallied with less than a minute worked, then clocked out before the payout
+        shift.tip_round = 1;
+        assert!(shift.is_on_shift());
+
+        shift.tip_paid = true;
+        assert!(!shift.is_on_shift());
+    }
+}
@@ -0,0 +1,1006 @@
+use anchor_lang::{prelude::*, system_program::{transfer, Transfer}};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+        state::Account as TokenAccountState,
+    },
+    token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked},
+};
+use crate::{
+    state::{
+        Combo, ComboSubstitution, Employee, IngredientList, InventoryItem, InventoryLot, MenuCategoryType, MenuItem,
+        ModifierGroup, ModifierSelection, PriceAdjustment, PriceSchedule, Promotion, PromotionRule, Restaurant, Shift,
+    },
+    constants::BPS_DENOMINATOR,
+    errors::{BuyingError, InventoryError, MenuError, OrderError, TipError},
+};
+
+/*
+    Ed25519 Instruction Helpers
+
+    Functionality:
+    - Reads the Ed25519SignatureOffsets header of an Ed25519 program instruction
+    - Returns the public key and message that the Ed25519 program verified
+
+    Security checks:
+    - Only a single signature is accepted
+    - Signature, public key and message must all live in the Ed25519 instruction itself
+*/
+
+// [num_signatures: u8, padding: u8] followed by one Ed25519SignatureOffsets (7 x u16)
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+pub fn load_ed25519_message(data: &[u8]) -> Result<(Pubkey, Vec<u8>)> {
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        BuyingError::InvalidInstruction
+    );
+
+    let read_u16 = |index: usize| {
+        let offset = ED25519_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[offset], data[offset + 1]])
+    };
+
+    let signature_instruction_index = read_u16(1);
+    let public_key_offset = read_u16(2) as usize;
+    let public_key_instruction_index = read_u16(3);
+    let message_data_offset = read_u16(4) as usize;
+    let message_data_size = read_u16(5) as usize;
+    let message_instruction_index = read_u16(6);
+
+    // u16::MAX means "this instruction", anything else could point at attacker controlled data
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        BuyingError::InvalidInstruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(BuyingError::InvalidInstruction)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(BuyingError::InvalidInstruction)?;
+
+    let public_key = Pubkey::try_from(public_key).map_err(|_| BuyingError::InvalidInstruction)?;
+
+    Ok((public_key, message.to_vec()))
+}
+
+/*
+    Order Item Helpers
+
+    Functionality:
+    - Loads the MenuItem and IngredientList of every order line, rejecting menu items of another restaurant
+    - Prices the order on-chain from MenuItem.price times quantity, or from the menu item's PriceSchedule
+      when one of its windows matches the restaurant's local time
+    - Validates the selected modifiers against each ModifierGroup's min/max and adds their price deltas
+    - Resolves the component MenuItems of combo items, applying the line's slot substitutions
+    - Computes promotion discounts and the sales tax per MenuCategoryType from the restaurant's rates
+    - Totals the stock the order takes from each referenced InventoryItem, including the inventory impact
+      of selected modifier options and of every combo component
+    - Depletes that stock when the order is placed and restores exactly the same quantities when it is cancelled
+    - Emits LowStock for every inventory item lowered below its reorder threshold
+
+    Remaining accounts layout, one group per order line in the same order as the lines:
+    - [menu_item, ingredient_list, inventory_item_0, ..., inventory_item_n, price_schedule,
+       modifier_group_0, ..., modifier_group_k, option_inventory_item_0, ..., option_inventory_item_m]
+    - Inventory items must follow the order of IngredientList.ingredients and be writable
+    - price_schedule is only passed when MenuItem.price_schedule is set
+    - Every group of MenuItem.modifier_groups is passed in order, followed by the inventory items of each
+      selected option (in selection order, following ModifierOption.ingredients)
+    - Combo items (MenuItem.combo set) then pass [combo, component_menu_item, component_ingredient_list,
+      component_inventory_item...] with one component group per Combo slot, the substitute when the slot is substituted
+*/
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum StockMovement {
+    Deplete,
+    Restore,
+}
+
+pub struct LineRequest {
+    pub menu_item: Pubkey,
+    pub quantity: u64,
+    pub modifiers: Vec<ModifierSelection>,
+    pub substitutions: Vec<ComboSubstitution>,
+}
+
+pub struct ComboComponent<'info> {
+    pub menu_item: Account<'info, MenuItem>,
+    pub quantity: u64, // units of the component per combo
+}
+
+pub struct OrderItem<'info> {
+    pub menu_item: Account<'info, MenuItem>,
+    pub quantity: u64,
+    pub unit_price: u64, // MenuItem.price, adjusted by its PriceSchedule and the selected modifiers
+    pub ingredients: Vec<(&'info AccountInfo<'info>, u64)>, // (InventoryItem account, quantity per unit)
+    pub components: Vec<ComboComponent<'info>>, // empty unless the menu item is a combo
+}
+
+/// Weekday (0 = Sunday) and minute of the day in a restaurant's local time
+pub struct LocalTime {
+    pub weekday: u8,
+    pub minute: u16,
+}
+
+impl LocalTime {
+    pub fn at(unix_timestamp: i64, utc_offset_minutes: i16) -> Self {
+        let local = unix_timestamp + utc_offset_minutes as i64 * 60;
+        let days = local.div_euclid(24 * 60 * 60);
+
+        LocalTime {
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7) as u8,
+            minute: (local.rem_euclid(24 * 60 * 60) / 60) as u16,
+        }
+    }
+}
+
+/// Price of a menu item at `time`, from the first matching window of its schedule
+pub fn scheduled_price(schedule: &PriceSchedule, base_price: u64, time: &LocalTime) -> Result<u64> {
+    let window = schedule.windows.iter().find(|window| {
+        window.days & (1 << time.weekday) != 0
+            && window.start_minute <= time.minute
+            && time.minute < window.end_minute
+    });
+
+    match window.map(|window| &window.adjustment) {
+        Some(PriceAdjustment::Price { amount }) => Ok(*amount),
+        Some(PriceAdjustment::PercentOff { bps }) => Ok(base_price - bps_of(base_price, *bps)?),
+        None => Ok(base_price),
+    }
+}
+
+pub fn load_order_items<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    restaurant: &Pubkey,
+    lines: &[LineRequest],
+    local_time: &LocalTime,
+) -> Result<Vec<OrderItem<'info>>> {
+    let mut accounts = remaining_accounts.iter();
+    let mut order_items = Vec::with_capacity(lines.len());
+
+    for line in lines.iter() {
+        require!(line.quantity > 0, OrderError::InvalidQuantity);
+
+        let menu_item_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+        require_keys_eq!(menu_item_info.key(), line.menu_item, MenuError::InvalidMenuItem);
+        let menu_item = Account::<MenuItem>::try_from(menu_item_info)?;
+        require_keys_eq!(menu_item.restaurant, *restaurant, MenuError::InvalidMenuItem);
+
+        let ingredient_list_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+        let ingredient_list = Account::<IngredientList>::try_from(ingredient_list_info)?;
+        require_keys_eq!(ingredient_list.menu_item, menu_item.key(), InventoryError::InvalidIngredientList);
+
+        let mut ingredients = Vec::with_capacity(ingredient_list.ingredients.len());
+        for (inventory_key, per_unit) in ingredient_list.ingredients.iter() {
+            let inventory_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+            require_keys_eq!(inventory_info.key(), *inventory_key, InventoryError::InventoryItemMismatch);
+            ingredients.push((inventory_info, *per_unit));
+        }
+
+        let base_price = match menu_item.price_schedule {
+            Some(price_schedule_key) => {
+                let price_schedule_info = accounts.next().ok_or(MenuError::MissingPriceSchedule)?;
+                require_keys_eq!(price_schedule_info.key(), price_schedule_key, MenuError::MissingPriceSchedule);
+                let price_schedule = Account::<PriceSchedule>::try_from(price_schedule_info)?;
+                scheduled_price(&price_schedule, menu_item.price, local_time)?
+            },
+            None => menu_item.price,
+        };
+
+        // Every modifier group of the menu item, then the inventory items of each selected option
+        let mut modifier_groups = Vec::with_capacity(menu_item.modifier_groups.len());
+        for modifier_group_key in menu_item.modifier_groups.iter() {
+            let modifier_group_info = accounts.next().ok_or(MenuError::MissingModifierGroup)?;
+            require_keys_eq!(modifier_group_info.key(), *modifier_group_key, MenuError::MissingModifierGroup);
+            modifier_groups.push(Account::<ModifierGroup>::try_from(modifier_group_info)?);
+        }
+
+        let mut price_delta = 0i128;
+        for selection in line.modifiers.iter() {
+            let option = modifier_groups
+                .get(selection.group as usize)
+                .and_then(|modifier_group| modifier_group.options.get(selection.option as usize))
+                .ok_or(MenuError::InvalidModifier)?;
+            price_delta += option.price_delta as i128;
+
+            for (inventory_key, per_unit) in option.ingredients.iter() {
+                let inventory_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+                require_keys_eq!(inventory_info.key(), *inventory_key, InventoryError::InventoryItemMismatch);
+                ingredients.push((inventory_info, *per_unit));
+            }
+        }
+
+        for (index, modifier_group) in modifier_groups.iter().enumerate() {
+            let selected = line.modifiers
+                .iter()
+                .filter(|selection| selection.group as usize == index)
+                .collect::<Vec<_>>();
+            let unique = selected
+                .iter()
+                .enumerate()
+                .all(|(position, selection)| !selected[..position].contains(selection));
+            require!(
+                unique
+                    && selected.len() >= modifier_group.min_selections as usize
+                    && selected.len() <= modifier_group.max_selections as usize,
+                MenuError::InvalidModifierSelection
+            );
+        }
+
+        // Combo components, each depleting its own IngredientList times the slot quantity
+        let mut components = Vec::new();
+        match menu_item.combo {
+            Some(combo_key) => {
+                let combo_info = accounts.next().ok_or(MenuError::MissingCombo)?;
+                require_keys_eq!(combo_info.key(), combo_key, MenuError::MissingCombo);
+                let combo = Account::<Combo>::try_from(combo_info)?;
+
+                require!(
+                    line.substitutions.iter().all(|substitution| (substitution.slot as usize) < combo.slots.len()),
+                    MenuError::InvalidSubstitution
+                );
+
+                for (index, slot) in combo.slots.iter().enumerate() {
+                    let mut substitutions = line.substitutions
+                        .iter()
+                        .filter(|substitution| substitution.slot as usize == index);
+                    let component_key = match (substitutions.next(), substitutions.next()) {
+                        (None, _) => slot.menu_item,
+                        (Some(substitution), None) => *slot.substitutes
+                            .get(substitution.substitute as usize)
+                            .ok_or(MenuError::InvalidSubstitution)?,
+                        (Some(_), Some(_)) => return Err(MenuError::InvalidSubstitution.into()),
+                    };
+
+                    let component_info = accounts.next().ok_or(MenuError::MissingCombo)?;
+                    require_keys_eq!(component_info.key(), component_key, MenuError::InvalidComboComponent);
+                    let component = Account::<MenuItem>::try_from(component_info)?;
+                    require!(
+                        component.restaurant == *restaurant && component.category != MenuCategoryType::Combo,
+                        MenuError::InvalidComboComponent
+                    );
+
+                    let component_list_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+                    let component_list = Account::<IngredientList>::try_from(component_list_info)?;
+                    require_keys_eq!(component_list.menu_item, component.key(), InventoryError::InvalidIngredientList);
+
+                    for (inventory_key, per_unit) in component_list.ingredients.iter() {
+                        let inventory_info = accounts.next().ok_or(InventoryError::MissingIngredientAccounts)?;
+                        require_keys_eq!(inventory_info.key(), *inventory_key, InventoryError::InventoryItemMismatch);
+                        let per_combo = per_unit.checked_mul(slot.quantity as u64).ok_or(BuyingError::Overflow)?;
+                        ingredients.push((inventory_info, per_combo));
+                    }
+
+                    components.push(ComboComponent { menu_item: component, quantity: slot.quantity as u64 });
+                }
+            },
+            None => require!(line.substitutions.is_empty(), MenuError::InvalidSubstitution),
+        }
+
+        let unit_price = u64::try_from(base_price as i128 + price_delta).map_err(|_| MenuError::InvalidModifier)?;
+
+        order_items.push(OrderItem {
+            menu_item,
+            quantity: line.quantity,
+            unit_price,
+            ingredients,
+            components,
+        });
+    }
+
+    Ok(order_items)
+}
+
+pub fn order_total(order_items: &[OrderItem]) -> Result<u64> {
+    let mut total: u64 = 0;
+
+    for item in order_items {
+        require!(item.menu_item.active, OrderError::InactiveMenuItem);
+        require!(
+            item.components.iter().all(|component| component.menu_item.active),
+            OrderError::InactiveMenuItem
+        );
+
+        total = item.unit_price
+            .checked_mul(item.quantity)
+            .and_then(|line_total| total.checked_add(line_total))
+            .ok_or(BuyingError::Overflow)?;
+    }
+
+    Ok(total)
+}
+
+/// `bps` basis points of `amount`, rounded half up
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let value = (amount as u128 * bps as u128 + BPS_DENOMINATOR as u128 / 2) / BPS_DENOMINATOR as u128;
+
+    u64::try_from(value).map_err(|_| BuyingError::Overflow.into())
+}
+
+/// Sales tax on the order, taxing each MenuCategoryType at its restaurant rate.
+/// `exempt` removes discounted amounts (e.g. a redeemed reward item) from their category,
+/// `prorated_discount` is a discount not tied to a category, spread across categories pro rata.
+pub fn order_tax(
+    order_items: &[OrderItem],
+    restaurant: &Restaurant,
+    exempt: &[(&MenuCategoryType, u64)],
+    prorated_discount: u64,
+) -> Result<u64> {
+    let mut taxable = [0u64; 7];
+
+    for item in order_items {
+        let line_total = item.unit_price
+            .checked_mul(item.quantity)
+            .ok_or(BuyingError::Overflow)?;
+        let category = item.menu_item.category.clone() as usize;
+        taxable[category] = taxable[category].checked_add(line_total).ok_or(BuyingError::Overflow)?;
+    }
+
+    for (category, amount) in exempt {
+        let category = (*category).clone() as usize;
+        taxable[category] = taxable[category].saturating_sub(*amount);
+    }
+
+    let taxable_total = taxable.iter().try_fold(0u64, |sum, amount| sum.checked_add(*amount)).ok_or(BuyingError::Overflow)?;
+    if prorated_discount > 0 && taxable_total > 0 {
+        let discount = prorated_discount.min(taxable_total);
+        for amount in taxable.iter_mut() {
+            let share = (*amount as u128 * discount as u128 / taxable_total as u128) as u64;
+            *amount -= share;
+        }
+    }
+
+    let mut tax: u64 = 0;
+    for (category, amount) in taxable.iter().enumerate() {
+        let bps = restaurant.category_tax_bps[category].unwrap_or(restaurant.tax_bps);
+        tax = tax.checked_add(bps_of(*amount, bps)?).ok_or(BuyingError::Overflow)?;
+    }
+
+    Ok(tax)
+}
+
+/// Discount granted by `promotion` on the order items within its category scope
+pub fn promotion_discount(order_items: &[OrderItem], promotion: &Promotion) -> Result<u64> {
+    let mut eligible: Vec<(u64, u64)> = order_items
+        .iter()
+        .filter(|item| promotion.category.as_ref().map_or(true, |category| *category == item.menu_item.category))
+        .map(|item| (item.unit_price, item.quantity))
+        .collect();
+
+    let eligible_total = eligible
+        .iter()
+        .try_fold(0u64, |sum, (price, quantity)| price.checked_mul(*quantity).and_then(|line| sum.checked_add(line)))
+        .ok_or(BuyingError::Overflow)?;
+
+    match promotion.rule {
+        PromotionRule::PercentOff { bps } => bps_of(eligible_total, bps),
+        PromotionRule::FixedOff { amount } => Ok(amount.min(eligible_total)),
+        PromotionRule::BuyXGetY { buy, get } => {
+            // The cheapest `get` units of every `buy + get` eligible units are free
+            let units = eligible.iter().map(|(_, quantity)| *quantity).sum::<u64>();
+            let mut free_units = units / (buy as u64 + get as u64) * get as u64;
+
+            eligible.sort_by_key(|(price, _)| *price);
+            let mut discount: u64 = 0;
+            for (price, quantity) in eligible {
+                let free = quantity.min(free_units);
+                discount = price
+                    .checked_mul(free)
+                    .and_then(|amount| discount.checked_add(amount))
+                    .ok_or(BuyingError::Overflow)?;
+                free_units -= free;
+            }
+
+            Ok(discount)
+        }
+    }
+}
+
+/// Totals the stock the order items take from each InventoryItem, in the order the items are first used
+pub fn stock_taken<'info>(order_items: &[OrderItem<'info>]) -> Result<Vec<(&'info AccountInfo<'info>, u64)>> {
+    let mut taken: Vec<(&'info AccountInfo<'info>, u64)> = Vec::new();
+
+    for item in order_items {
+        for (inventory_info, per_unit) in item.ingredients.iter() {
+            let quantity = per_unit.checked_mul(item.quantity).ok_or(BuyingError::Overflow)?;
+            if quantity == 0 {
+                continue;
+            }
+
+            // Inventory items shared between menu items, modifiers and combo components are taken once
+            match taken.iter_mut().find(|(info, _)| info.key == inventory_info.key) {
+                Some((_, total)) => *total = total.checked_add(quantity).ok_or(BuyingError::Overflow)?,
+                None => taken.push((*inventory_info, quantity)),
+            }
+        }
+    }
+
+    Ok(taken)
+}
+
+/// Depletes or restores the given quantity of every (InventoryItem account, quantity) pair
+pub fn move_ingredient_stock<'info>(stock: &[(&'info AccountInfo<'info>, u64)], movement: StockMovement, restaurant: &Pubkey) -> Result<()> {
+    for (inventory_info, quantity) in stock.iter() {
+        let mut inventory_item = Account::<InventoryItem>::try_from(inventory_info)?;
+        let previous_stock = inventory_item.stock;
+        match movement {
+            StockMovement::Deplete => {
+                inventory_item.consume(*quantity).ok_or(InventoryError::InsufficientStock)?;
+            }
+            // Restored units go back as untracked stock, the lots they came from may be gone
+            StockMovement::Restore => {
+                inventory_item.stock = inventory_item.stock
+                    .checked_add(*quantity)
+                    .ok_or(BuyingError::Overflow)?;
+            }
+        }
+        check_low_stock(&inventory_item, previous_stock, restaurant);
+        inventory_item.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Adds a received batch to `inventory_item` as its newest lot, or merges it into the newest lot
+/// when both have the same unit cost and expiry
+pub fn receive_lot(inventory_item: &mut InventoryItem, quantity: u64, unit_cost: u64, expires_at: Option<i64>, now: i64) -> Result<()> {
+    require!(quantity > 0, InventoryError::InvalidLot);
+    require!(expires_at.map_or(true, |expires_at| expires_at > now), InventoryError::InvalidLot);
+
+    inventory_item.stock = inventory_item.stock.checked_add(quantity).ok_or(BuyingError::Overflow)?;
+    inventory_item.last_order = now;
+    match inventory_item.lots.last_mut() {
+        Some(newest) if newest.unit_cost == unit_cost && newest.expires_at == expires_at => {
+            newest.quantity = newest.quantity.checked_add(quantity).ok_or(BuyingError::Overflow)?;
+        }
+        _ => {
+            require!(inventory_item.lots.len() < InventoryItem::MAX_LOTS, InventoryError::TooManyLots);
+            inventory_item.lots.push(InventoryLot {
+                quantity,
+                unit_cost,
+                received_at: now,
+                expires_at,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Emits LowStock when `inventory_item` was lowered from `previous_stock` to below its reorder threshold
+pub fn check_low_stock(inventory_item: &Account<InventoryItem>, previous_stock: u64, restaurant: &Pubkey) {
+    if inventory_item.stock < previous_stock && inventory_item.stock < inventory_item.reorder_threshold {
+        emit!(LowStock {
+            inventory_item: inventory_item.key(),
+            sku: inventory_item.sku.clone(),
+            stock: inventory_item.stock,
+            reorder_threshold: inventory_item.reorder_threshold,
+            par_level: inventory_item.par_level,
+            shortfall: inventory_item.par_level.saturating_sub(inventory_item.stock),
+            restaurant: *restaurant,
+        });
+    }
+}
+
+// Event emitted whenever an instruction lowers an inventory item's stock below its reorder threshold
+#[event]
+pub struct LowStock {
+    pub inventory_item: Pubkey,
+    pub sku: String,
+    pub stock: u64,
+    pub reorder_threshold: u64,
+    pub par_level: u64,
+    pub shortfall: u64, // units needed to get back to the par level
+    pub restaurant: Pubkey,
+}
+
+/// Moves the combo_references of the menu items a combo uses from its `previous` to its `next` slot items.
+/// `menu_item_accounts` holds every item of `previous`, then every item of `next` not already in `previous`, writable
+pub fn update_combo_references<'info>(
+    menu_item_accounts: &'info [AccountInfo<'info>],
+    restaurant: &Pubkey,
+    previous: &[Pubkey],
+    next: &[Pubkey],
+) -> Result<()> {
+    let mut affected: Vec<Pubkey> = previous.to_vec();
+    affected.extend(next.iter().filter(|key| !previous.contains(key)));
+    require!(menu_item_accounts.len() == affected.len(), MenuError::InvalidComboComponent);
+
+    for (menu_item_info, key) in menu_item_accounts.iter().zip(affected.iter()) {
+        require_keys_eq!(menu_item_info.key(), *key, MenuError::InvalidComboComponent);
+        let mut menu_item = Account::<MenuItem>::try_from(menu_item_info)?;
+        require_keys_eq!(menu_item.restaurant, *restaurant, MenuError::InvalidComboComponent);
+
+        match (previous.contains(key), next.contains(key)) {
+            (false, true) => {
+                menu_item.combo_references = menu_item.combo_references.checked_add(1).ok_or(BuyingError::Overflow)?;
+            }
+            (true, false) => {
+                menu_item.combo_references = menu_item.combo_references.saturating_sub(1);
+            }
+            _ => {}
+        }
+        menu_item.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/*
+    Payment Helpers
+
+    Functionality:
+    - Restaurants that take native SOL use the default Pubkey as their currency
+    - Token payments go through transfer_checked so both SPL Token and Token-2022 mints work
+    - Lamports held by accounts owned by this program (restaurant, order escrow) are moved directly
+*/
+
+pub const NATIVE_SOL_DECIMALS: u8 = 9;
+
+pub fn is_native_sol(currency: &Pubkey) -> bool {
+    *currency == Pubkey::default()
+}
+
+/// Reward points earned for `amount` base units at `points_per_token` points per whole token
+pub fn reward_points_for(amount: u64, points_per_token: u64, decimals: u8) -> Result<u64> {
+    let points = (amount as u128)
+        .checked_mul(points_per_token as u128)
+        .ok_or(BuyingError::Overflow)?
+        / 10u128.pow(decimals as u32);
+
+    u64::try_from(points).map_err(|_| BuyingError::Overflow.into())
+}
+
+/// Checks that `destination` can receive a payout for `wallet`: the wallet itself for native SOL,
+/// otherwise a token account of `currency` owned by the wallet
+pub fn check_payout_destination<'info>(currency: &Pubkey, destination: &'info AccountInfo<'info>, wallet: &Pubkey) -> Result<()> {
+    if is_native_sol(currency) {
+        require_keys_eq!(destination.key(), *wallet, BuyingError::InvalidPayoutDestination);
+    } else {
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(destination)?;
+        require_keys_eq!(token_account.owner, *wallet, BuyingError::InvalidPayoutDestination);
+        require_keys_eq!(token_account.mint, *currency, BuyingError::InvalidCurrency);
+    }
+
+    Ok(())
+}
+
+/// Loads an employee's Shift and Employee accounts, checking both are the restaurant's PDAs for the shift's
+/// wallet. A removed employee's PDA is closed and is returned as None
+pub fn load_tip_shift<'info>(
+    restaurant: &Pubkey,
+    employee_info: &'info AccountInfo<'info>,
+    shift_info: &'info AccountInfo<'info>,
+) -> Result<(Option<Account<'info, Employee>>, Account<'info, Shift>)> {
+    let shift = Account::<Shift>::try_from(shift_info)?;
+    let expected_shift = Pubkey::create_program_address(
+        &[b"shift", restaurant.as_ref(), shift.wallet.as_ref(), &[shift.bump]],
+        &crate::ID,
+    ).map_err(|_| TipError::InvalidEmployeeAccounts)?;
+    require_keys_eq!(shift_info.key(), expected_shift, TipError::InvalidEmployeeAccounts);
+
+    if employee_info.data_is_empty() {
+        let (expected_employee, _) = Pubkey::find_program_address(
+            &[b"employee", restaurant.as_ref(), shift.wallet.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(employee_info.key(), expected_employee, TipError::InvalidEmployeeAccounts);
+        return Ok((None, shift));
+    }
+
+    let employee = Account::<Employee>::try_from(employee_info)?;
+    let expected_employee = Pubkey::create_program_address(
+        &[b"employee", restaurant.as_ref(), shift.wallet.as_ref(), &[employee.bump]],
+        &crate::ID,
+    ).map_err(|_| TipError::InvalidEmployeeAccounts)?;
+    require_keys_eq!(employee_info.key(), expected_employee, TipError::InvalidEmployeeAccounts);
+    require_keys_eq!(employee.restaurant, *restaurant, TipError::EmployeeRestaurantMismatch);
+
+    Ok((Some(employee), shift))
+}
+
+pub fn transfer_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from,
+                mint: mint.to_account_info(),
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Closes a program-owned token account once it is empty, returning its rent to `destination`.
+/// Accounts still holding tokens or withheld transfer fees are left open, so they never block the caller
+pub fn close_empty_token_account<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    account: &mut InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<bool> {
+    account.reload()?;
+    if account.amount > 0 {
+        return Ok(false);
+    }
+
+    let withheld = {
+        let info = account.to_account_info();
+        let data = info.try_borrow_data()?;
+        StateWithExtensions::<TokenAccountState>::unpack(&data)
+            .ok()
+            .and_then(|state| state.get_extension::<TransferFeeAmount>().ok().map(|fee| u64::from(fee.withheld_amount)))
+            .unwrap_or(0)
+    };
+    if withheld > 0 {
+        return Ok(false);
+    }
+
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(true)
+}
+
+/// Reallocates a program account to `new_len`, topping up rent from `payer` when it grows
+/// and returning the freed rent to `payer` when it shrinks
+pub fn resize_program_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let minimum_balance = Rent::get()?.minimum_balance(new_len);
+    let balance = account.lamports();
+
+    if minimum_balance > balance {
+        transfer(
+            CpiContext::new(system_program.clone(), Transfer { from: payer.clone(), to: account.clone() }),
+            minimum_balance - balance,
+        )?;
+    }
+    account.realloc(new_len, false)?;
+    if balance > minimum_balance {
+        transfer_program_lamports(account, payer, balance - minimum_balance)?;
+    }
+
+    Ok(())
+}
+
+pub fn transfer_program_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    // Never drain a program account below rent exemption
+    let minimum_balance = Rent::get()?.minimum_balance(from.data_len());
+    let from_balance = from.lamports().checked_sub(amount).ok_or(BuyingError::Underflow)?;
+    require!(from_balance >= minimum_balance, BuyingError::Underflow);
+    let to_balance = to.lamports().checked_add(amount).ok_or(BuyingError::Overflow)?;
+
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::state::{InventoryCategoryType, PriceWindow, RestaurantType};
+
+    fn promotion(rule: PromotionRule, category: Option<MenuCategoryType>) -> Promotion {
+        Promotion {
+            restaurant: Pubkey::default(),
+            code: "CODE".to_string(),
+            rule,
+            category,
+            starts_at: 0,
+            ends_at: i64::MAX,
+            max_redemptions: None,
+            per_customer_limit: None,
+            redemptions: 0,
+            active: true,
+            bump: 255,
+        }
+    }
+
+    /// A MenuItem account owned by this program, leaked so it can back an OrderItem
+    pub(crate) fn menu_item_account(category: MenuCategoryType, price: u64) -> Account<'static, MenuItem> {
+        let menu_item = MenuItem {
+            restaurant: Pubkey::default(),
+            sku: "sku".to_string(),
+            category,
+            name: "item".to_string(),
+            price,
+            description: String::new(),
+            active: true,
+            price_schedule: None,
+            modifier_groups: Vec::new(),
+            combo: None,
+            active_vouchers: 0,
+            combo_references: 0,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        menu_item.try_serialize(&mut data).unwrap();
+
+        let info = Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            false,
+            Box::leak(Box::new(0u64)),
+            Box::leak(data.into_boxed_slice()),
+            &crate::ID,
+            false,
+            0,
+        )));
+        Account::try_from(&*info).unwrap()
+    }
+
+    pub(crate) fn order_item(category: MenuCategoryType, unit_price: u64, quantity: u64) -> OrderItem<'static> {
+        OrderItem {
+            menu_item: menu_item_account(category, unit_price),
+            quantity,
+            unit_price,
+            ingredients: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn inventory_item(stock: u64) -> InventoryItem {
+        InventoryItem {
+            sku: "sku".to_string(),
+            category: InventoryCategoryType::Food,
+            name: "item".to_string(),
+            price: 0,
+            stock,
+            last_order: 0,
+            reorder_threshold: 0,
+            par_level: 0,
+            auto_deactivate: false,
+            lots: Vec::new(),
+            initialized: true,
+            bump: 255,
+        }
+    }
+
+    /// An InventoryItem account owned by this program, leaked so it can back an order's ingredients
+    fn inventory_account(stock: u64) -> &'static AccountInfo<'static> {
+        let mut data = Vec::new();
+        inventory_item(stock).try_serialize(&mut data).unwrap();
+
+        Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            true,
+            Box::leak(Box::new(0u64)),
+            Box::leak(data.into_boxed_slice()),
+            &crate::ID,
+            false,
+            0,
+        )))
+    }
+
+    fn stock_of(info: &'static AccountInfo<'static>) -> u64 {
+        Account::<InventoryItem>::try_from(info).unwrap().stock
+    }
+
+    fn restaurant(tax_bps: u16, category_tax_bps: [Option<u16>; 7]) -> Restaurant {
+        Restaurant {
+            restaurant_type: RestaurantType::Restaurant,
+            owner: Pubkey::default(),
+            name: String::new(),
+            symbol: String::new(),
+            currency: Pubkey::default(),
+            url: String::new(),
+            customer_count: 0,
+            reward_points_per_token: 1,
+            tax_bps,
+            category_tax_bps,
+            service_charge_bps: 0,
+            utc_offset_minutes: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn bps_of_rounds_half_up() {
+        assert_eq!(bps_of(1_000, 825).unwrap(), 83); // 82.5
+        assert_eq!(bps_of(1_000, 824).unwrap(), 82); // 82.4
+        assert_eq!(bps_of(0, 10_000).unwrap(), 0);
+        assert_eq!(bps_of(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn order_tax_uses_category_overrides() {
+        let mut category_tax_bps = [None; 7];
+        category_tax_bps[MenuCategoryType::Alcohol as usize] = Some(2_000);
+        let restaurant = restaurant(1_000, category_tax_bps);
+        let items = [
+            order_item(MenuCategoryType::Entree, 1_000, 2),
+            order_item(MenuCategoryType::Alcohol, 500, 1),
+        ];
+
+        assert_eq!(order_tax(&items, &restaurant, &[], 0).unwrap(), 200 + 100);
+    }
+
+    #[test]
+    fn order_tax_skips_exempt_and_prorated_discounts() {
+        let restaurant = restaurant(1_000, [None; 7]);
+        let items = [
+            order_item(MenuCategoryType::Entree, 1_000, 1),
+            order_item(MenuCategoryType::Dessert, 1_000, 1),
+        ];
+
+        // The redeemed dessert is not taxed
+        assert_eq!(order_tax(&items, &restaurant, &[(&MenuCategoryType::Dessert, 1_000)], 0).unwrap(), 100);
+        // 500 off is spread across both categories
+        assert_eq!(order_tax(&items, &restaurant, &[], 500).unwrap(), 150);
+        // A discount above the taxable total leaves nothing to tax
+        assert_eq!(order_tax(&items, &restaurant, &[], 5_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn local_time_applies_the_utc_offset() {
+        // 1970-01-01 00:00 UTC was a Thursday
+        let time = LocalTime::at(0, 0);
+        assert_eq!((time.weekday, time.minute), (4, 0));
+
+        // Wednesday 23:00 at UTC-1
+        let time = LocalTime::at(0, -60);
+        assert_eq!((time.weekday, time.minute), (3, 23 * 60));
+
+        // Sunday 2024-01-07 12:30 UTC, 14:00 at UTC+1:30
+        let time = LocalTime::at(1_704_630_600, 90);
+        assert_eq!((time.weekday, time.minute), (0, 14 * 60));
+    }
+
+    #[test]
+    fn scheduled_price_uses_the_first_matching_window() {
+        let schedule = PriceSchedule {
+            menu_item: Pubkey::default(),
+            windows: vec![
+                PriceWindow {
+                    days: 0b0011_1110, // Monday to Friday
+                    start_minute: 16 * 60,
+                    end_minute: 18 * 60,
+                    adjustment: PriceAdjustment::PercentOff { bps: 2_500 },
+                },
+                PriceWindow {
+                    days: 0b0111_1111,
+                    start_minute: 16 * 60,
+                    end_minute: 19 * 60,
+                    adjustment: PriceAdjustment::Price { amount: 700 },
+                },
+            ],
+            bump: 255,
+        };
+        let at = |weekday, minute| LocalTime { weekday, minute };
+
+        assert_eq!(scheduled_price(&schedule, 1_000, &at(1, 16 * 60)).unwrap(), 750);
+        // The end minute is exclusive
+        assert_eq!(scheduled_price(&schedule, 1_000, &at(1, 18 * 60)).unwrap(), 700);
+        assert_eq!(scheduled_price(&schedule, 1_000, &at(6, 17 * 60)).unwrap(), 700);
+        assert_eq!(scheduled_price(&schedule, 1_000, &at(1, 19 * 60)).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn promotion_discount_applies_each_rule() {
+        let items = [
+            order_item(MenuCategoryType::Entree, 1_000, 2),
+            order_item(MenuCategoryType::Beverage, 300, 1),
+        ];
+
+        assert_eq!(promotion_discount(&items, &promotion(PromotionRule::PercentOff { bps: 1_000 }, None)).unwrap(), 230);
+        assert_eq!(promotion_discount(&items, &promotion(PromotionRule::FixedOff { amount: 500 }, None)).unwrap(), 500);
+        // Fixed discounts never exceed the eligible total
+        assert_eq!(promotion_discount(&items, &promotion(PromotionRule::FixedOff { amount: 5_000 }, None)).unwrap(), 2_300);
+    }
+
+    #[test]
+    fn promotion_discount_is_limited_to_its_category() {
+        let items = [
+            order_item(MenuCategoryType::Entree, 1_000, 2),
+            order_item(MenuCategoryType::Beverage, 300, 1),
+        ];
+        let promotion = promotion(PromotionRule::PercentOff { bps: 5_000 }, Some(MenuCategoryType::Beverage));
+
+        assert_eq!(promotion_discount(&items, &promotion).unwrap(), 150);
+    }
+
+    #[test]
+    fn buy_x_get_y_frees_the_cheapest_units() {
+        let items = [
+            order_item(MenuCategoryType::Entree, 1_000, 3),
+            order_item(MenuCategoryType::Side, 200, 2),
+            order_item(MenuCategoryType::Dessert, 400, 1),
+        ];
+
+        // 6 units buy 2 get 1: the 2 cheapest units are free
+        assert_eq!(promotion_discount(&items, &promotion(PromotionRule::BuyXGetY { buy: 2, get: 1 }, None)).unwrap(), 400);
+        // Not enough units for a free one
+        let items = [order_item(MenuCategoryType::Entree, 1_000, 2)];
+        assert_eq!(promotion_discount(&items, &promotion(PromotionRule::BuyXGetY { buy: 2, get: 1 }, None)).unwrap(), 0);
+    }
+
+    #[test]
+    fn receive_lot_merges_matching_batches() {
+        let mut item = inventory_item(0);
+
+        receive_lot(&mut item, 5, 2, Some(1_000), 10).unwrap();
+        receive_lot(&mut item, 3, 2, Some(1_000), 20).unwrap();
+        assert_eq!(item.lots.len(), 1);
+        assert_eq!(item.lots[0].quantity, 8);
+        assert_eq!(item.stock, 8);
+
+        // Only the newest lot is merged into
+        receive_lot(&mut item, 1, 3, Some(1_000), 30).unwrap();
+        receive_lot(&mut item, 1, 2, Some(1_000), 40).unwrap();
+        assert_eq!(item.lots.len(), 3);
+
+        assert!(receive_lot(&mut item, 0, 2, None, 50).is_err());
+        assert!(receive_lot(&mut item, 1, 2, Some(50), 50).is_err());
+    }
+
+    #[test]
+    fn stock_taken_totals_shared_inventory_items() {
+        let (flour, salt, garnish) = (inventory_account(100), inventory_account(100), inventory_account(100));
+        let mut bread = order_item(MenuCategoryType::Entree, 1_000, 3);
+        bread.ingredients = vec![(flour, 2), (salt, 1), (garnish, 0)];
+        let mut roll = order_item(MenuCategoryType::Side, 200, 2);
+        roll.ingredients = vec![(flour, 1)];
+
+        let taken: Vec<(Pubkey, u64)> = stock_taken(&[bread, roll])
+            .unwrap()
+            .iter()
+            .map(|(info, quantity)| (info.key(), *quantity))
+            .collect();
+        // Flour is taken once for both lines, nothing is taken from the unused garnish
+        assert_eq!(taken, vec![(flour.key(), 3 * 2 + 2), (salt.key(), 3)]);
+    }
+
+    #[test]
+    fn move_ingredient_stock_restores_what_was_depleted() {
+        let (flour, salt) = (inventory_account(10), inventory_account(5));
+        let stock = [(flour, 8), (salt, 5)];
+
+        move_ingredient_stock(&stock, StockMovement::Deplete, &Pubkey::default()).unwrap();
+        assert_eq!((stock_of(flour), stock_of(salt)), (2, 0));
+
+        move_ingredient_stock(&stock, StockMovement::Restore, &Pubkey::default()).unwrap();
+        assert_eq!((stock_of(flour), stock_of(salt)), (10, 5));
+    }
+
+    #[test]
+    fn move_ingredient_stock_rejects_insufficient_stock() {
+        let flour = inventory_account(7);
+
+        let result = move_ingredient_stock(&[(flour, 8)], StockMovement::Deplete, &Pubkey::default());
+        assert_eq!(result.unwrap_err(), InventoryError::InsufficientStock.into());
+        assert_eq!(stock_of(flour), 7);
+    }
+}
@@ -0,0 +1,116 @@
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addEmployee, addInventoryItem, addMenuItem, cancelOrder, createRestaurant, EmployeeType, expectError, fundedKeypair,
+  ownerAccounts, pda, placeOrder, program, Status, TestMenuItem, TestRestaurant, updateOrder, writable,
+} from "./utils";
+
+const stockOf = async (item: PublicKey) => (await program.account.inventoryItem.fetch(item)).stock.toNumber();
+
+const removeMenuItem = async (restaurant: TestRestaurant, item: TestMenuItem) => {
+  const menu = pda(Buffer.from("menu"), restaurant.restaurant.toBuffer());
+  await program.methods
+    .restaurantToggleMenuItem({ sku: item.sku })
+    .accountsPartial({ item: item.menuItem, menu, ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+  await program.methods
+    .restaurantRemoveMenuItem({ sku: item.sku })
+    .accountsPartial({ menuItem: item.menuItem, ingredientList: item.ingredientList, menu, ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+};
+
+describe("cancel_order", () => {
+  it("Restores exactly the stock the order took", async () => {
+    const restaurant = await createRestaurant();
+    const flour = await addInventoryItem(restaurant, "flour", 10);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000, [[flour, 2]]);
+    const roll = await addMenuItem(restaurant, "roll", 500_000, [[flour, 1]]);
+    const customer = await fundedKeypair();
+
+    const { orderId, order } = await placeOrder(restaurant, customer, [[bread, 2], [roll, 3]]);
+    const placed = await program.account.customerOrder.fetch(order);
+    // Flour shared by both lines is recorded once
+    expect(placed.stockTaken.map((taken) => [taken.inventoryItem.toBase58(), taken.quantity.toNumber()]))
+      .to.deep.equal([[flour.toBase58(), 7]]);
+    expect(await stockOf(flour)).to.equal(3);
+
+    await cancelOrder(restaurant, customer, orderId, customer.publicKey);
+
+    expect(await stockOf(flour)).to.equal(10);
+  });
+
+  it("Restores the stock of a menu item removed since the order was placed", async () => {
+    const restaurant = await createRestaurant();
+    const flour = await addInventoryItem(restaurant, "flour", 10);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000, [[flour, 2]]);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    await removeMenuItem(restaurant, bread);
+    await cancelOrder(restaurant, customer, orderId, customer.publicKey);
+
+    expect(await stockOf(flour)).to.equal(10);
+  });
+
+  it("Team leader cancels an accepted order", async () => {
+    const restaurant = await createRestaurant();
+    const leader = await addEmployee(restaurant, EmployeeType.TeamLeader);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId, order } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    await updateOrder(restaurant, leader, orderId, Status.Accepted);
+    await cancelOrder(restaurant, leader.keypair, orderId, customer.publicKey, { accounts: { employee: leader.employee } });
+
+    expect((await program.account.customerOrder.fetch(order)).status).to.have.property("cancelled");
+  });
+
+  it("Rejects a cancellation by a team member", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+
+    await expectError(
+      cancelOrder(restaurant, member.keypair, orderId, customer.publicKey, { accounts: { employee: member.employee } }),
+      "Unauthorized",
+    );
+  });
+
+  it("Rejects a team leader cancelling an order already being prepared", async () => {
+    const restaurant = await createRestaurant();
+    const leader = await addEmployee(restaurant, EmployeeType.TeamLeader);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    await updateOrder(restaurant, leader, orderId, Status.Accepted);
+    await updateOrder(restaurant, leader, orderId, Status.Preparing);
+
+    await expectError(
+      cancelOrder(restaurant, leader.keypair, orderId, customer.publicKey, { accounts: { employee: leader.employee } }),
+      "Unauthorized",
+    );
+  });
+
+  it("Rejects inventory accounts that don't match the recorded stock", async () => {
+    const restaurant = await createRestaurant();
+    const flour = await addInventoryItem(restaurant, "flour", 10);
+    const sugar = await addInventoryItem(restaurant, "sugar", 10);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000, [[flour, 2]]);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+
+    await expectError(
+      cancelOrder(restaurant, customer, orderId, customer.publicKey, {
+        remainingAccounts: [writable(sugar)],
+      }),
+      "InventoryItemMismatch",
+    );
+  });
+});
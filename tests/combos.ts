@@ -0,0 +1,91 @@
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addInventoryItem, addMenuItem, createRestaurant, expectError, fundedKeypair, lineAccounts, ownerAccounts, pda,
+  placeOrder, program, readonly, TestMenuItem, TestRestaurant, writable,
+} from "./utils";
+
+// MenuCategoryType order: Combo, Side, Entree, ...
+const COMBO = 0;
+const SIDE = 1;
+
+const comboPda = (item: TestMenuItem) => pda(Buffer.from("combo"), item.menuItem.toBuffer());
+
+const comboAccounts = (restaurant: TestRestaurant, item: TestMenuItem) => ({
+  menuItem: item.menuItem,
+  combo: comboPda(item),
+  ...ownerAccounts(restaurant),
+});
+
+describe("combos", () => {
+  let restaurant: TestRestaurant;
+  let bun: PublicKey;
+  let burger: TestMenuItem;
+  let fries: TestMenuItem;
+  let salad: TestMenuItem;
+  let meal: TestMenuItem;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    bun = await addInventoryItem(restaurant, "bun", 100);
+    burger = await addMenuItem(restaurant, "burger", 900_000, [[bun, 1]]);
+    fries = await addMenuItem(restaurant, "fries", 400_000, [], SIDE);
+    salad = await addMenuItem(restaurant, "salad", 500_000, [], SIDE);
+    meal = await addMenuItem(restaurant, "meal", 1_200_000, [], COMBO);
+  });
+
+  it("Sells a combo at its bundle price with a substituted slot", async () => {
+    await program.methods
+      .restaurantSetCombo({
+        sku: meal.sku,
+        slots: [
+          { menuItem: burger.menuItem, quantity: 2, substitutes: [] },
+          { menuItem: fries.menuItem, quantity: 1, substitutes: [salad.menuItem] },
+        ],
+      })
+      .accountsPartial(comboAccounts(restaurant, meal))
+      .remainingAccounts([burger, fries, salad].map((item) => writable(item.menuItem)))
+      .signers([restaurant.owner])
+      .rpc();
+    expect((await program.account.menuItem.fetch(salad.menuItem)).comboReferences).to.equal(1);
+
+    const customer = await fundedKeypair();
+    const { order } = await placeOrder(restaurant, customer, [[meal, 1]], {
+      substitutions: [[{ slot: 1, substitute: 0 }]],
+      remainingAccounts: [
+        ...lineAccounts(meal),
+        readonly(comboPda(meal)),
+        ...lineAccounts(burger),
+        ...lineAccounts(salad),
+      ],
+    });
+
+    expect((await program.account.customerOrder.fetch(order)).total.toNumber()).to.equal(1_200_000);
+    expect((await program.account.inventoryItem.fetch(bun)).stock.toNumber()).to.equal(98);
+  });
+
+  it("Rejects slots on a menu item that isn't a combo", async () => {
+    await expectError(
+      program.methods
+        .restaurantSetCombo({ sku: burger.sku, slots: [{ menuItem: fries.menuItem, quantity: 1, substitutes: [] }] })
+        .accountsPartial(comboAccounts(restaurant, burger))
+        .remainingAccounts([writable(fries.menuItem)])
+        .signers([restaurant.owner])
+        .rpc(),
+      "NotACombo",
+    );
+  });
+
+  it("Removes the combo and releases its components", async () => {
+    await program.methods
+      .restaurantRemoveCombo({ sku: meal.sku })
+      .accountsPartial(comboAccounts(restaurant, meal))
+      .remainingAccounts([burger, fries, salad].map((item) => writable(item.menuItem)))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect((await program.account.menuItem.fetch(meal.menuItem)).combo).to.equal(null);
+    expect((await program.account.menuItem.fetch(salad.menuItem)).comboReferences).to.equal(0);
+    expect(await program.account.combo.fetchNullable(comboPda(meal))).to.equal(null);
+  });
+});
@@ -0,0 +1,63 @@
+import { expect } from "chai";
+import {
+  addEmployee, addMenuItem, balance, cancelOrder, completeOrder, createRestaurant, customerPda, EmployeeType,
+  expectError, fundedKeypair, placeOrder, program, Status, updateOrder,
+} from "./utils";
+
+describe("escrow", () => {
+  it("Holds an escrowed SOL payment in the order until it is completed", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const restaurantBefore = await balance(restaurant.restaurant);
+    const { orderId, order } = await placeOrder(restaurant, customer, [[bread, 1]], { escrow: true });
+
+    const placed = await program.account.customerOrder.fetch(order);
+    expect(placed.escrowExpiresAt).to.not.equal(null);
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore);
+
+    await completeOrder(restaurant, member, orderId);
+
+    const completed = await program.account.customerOrder.fetch(order);
+    expect(completed.escrowExpiresAt).to.equal(null);
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore + placed.paid.toNumber());
+  });
+
+  it("Refunds an escrowed SOL payment when the customer cancels", async () => {
+    const restaurant = await createRestaurant();
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId, order } = await placeOrder(restaurant, customer, [[bread, 1]], { escrow: true });
+    const paid = (await program.account.customerOrder.fetch(order)).paid.toNumber();
+    const before = await balance(customer.publicKey);
+
+    await cancelOrder(restaurant, customer, orderId, customer.publicKey);
+
+    const cancelled = await program.account.customerOrder.fetch(order);
+    expect(cancelled.status).to.have.property("cancelled");
+    expect(cancelled.refunded.toNumber()).to.equal(paid);
+    expect(await balance(customer.publicKey)).to.be.greaterThan(before);
+    // The cancelled order no longer counts towards the customer's orders and reward points
+    const account = await program.account.customer.fetch(customerPda(restaurant, customer.publicKey));
+    expect(account.totalOrders.toNumber()).to.equal(0);
+    expect(account.rewardPoints.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a customer cancellation once the escrowed order is accepted", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]], { escrow: true });
+    await updateOrder(restaurant, member, orderId, Status.Accepted);
+
+    await expectError(
+      cancelOrder(restaurant, customer, orderId, customer.publicKey),
+      "InvalidStatusTransition",
+    );
+  });
+});
@@ -112,6 +112,8 @@ describe("hestia_protocol", () => {
     symbol: restaurantSymbol,
     currency: mint.publicKey,
     url: url,
+    rewardPointsPerToken: new anchor.BN(10),
+    utcOffsetMinutes: 0,
     bump: newRestaurantPdaBump,
   }
 
@@ -177,6 +179,9 @@ describe("hestia_protocol", () => {
     price: new anchor.BN(10),
     stock: new anchor.BN(100),
     lastOrder: new anchor.BN(today.getTime()),
+    reorderThreshold: new anchor.BN(20),
+    parLevel: new anchor.BN(100),
+    autoDeactivate: false,
     initialized: false,
     bump: itemBump,
   };
@@ -205,6 +210,9 @@ describe("hestia_protocol", () => {
     price: new anchor.BN(1),
     stock: new anchor.BN(10),
     lastOrder: new anchor.BN(today.getTime()),
+    reorderThreshold: new anchor.BN(20),
+    parLevel: new anchor.BN(100),
+    autoDeactivate: false,
     initialized: true,
     bump: itemBump,
   };
@@ -226,7 +234,7 @@ describe("hestia_protocol", () => {
   });
 
   const menuItemSku = (Math.floor(Math.random() * 1000000)).toString();
-  const [menuItemPda, menuItemBump] = PublicKey.findProgramAddressSync([Buffer.from('item'), newRestaurant.publicKey.toBuffer(), Buffer.from(menuItemSku)], program.programId);
+  const [menuItemPda, menuItemBump] = PublicKey.findProgramAddressSync([Buffer.from('menu_item'), newRestaurant.publicKey.toBuffer(), Buffer.from(menuItemSku)], program.programId);
   const [menuPda, menuBump] = PublicKey.findProgramAddressSync([Buffer.from('menu'), newRestaurant.publicKey.toBuffer()], program.programId);
   const menuItemArgs = {
     sku: menuItemSku,
@@ -277,11 +285,22 @@ describe("hestia_protocol", () => {
     orderId: new anchor.BN(orderId),
     customer: customer.publicKey,
     customerName: 'Matt',
-    items: [new anchor.BN(menuItemSku)],
+    lines: [{
+      menuItem: menuItemPda,
+      quantity: 1,
+      modifiers: [],
+      substitutions: [],
+      note: null,
+    }],
     total: new anchor.BN(menuItemArgs.price),
     status: 0,
     createdAt: new anchor.BN(today.getDate()),
     updatedAt: null,
+    useReward: false,
+    promoCode: null,
+    escrow: false,
+    tip: new anchor.BN(0),
+    splitShares: [],
     bump: orderBump
   };
 
@@ -0,0 +1,92 @@
+import { expect } from "chai";
+import {
+  addInventoryItem, addMenuItem, bn, createRestaurant, expectError, fundedKeypair, inventoryItemPda, now, ownerAccounts,
+  pda, placeOrder, program, TestRestaurant,
+} from "./utils";
+
+// WasteReason order: Spoiled, Dropped, Comped, Theft, Expired
+const EXPIRED = 4;
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+const receiveLot = (restaurant: TestRestaurant, sku: string, quantity: number, unitCost: number, expiresAt: number | null = null) =>
+  program.methods
+    .restaurantReceiveInventoryLot({
+      sku,
+      quantity: bn(quantity),
+      unitCost: bn(unitCost),
+      expiresAt: expiresAt === null ? null : bn(expiresAt),
+    })
+    .accountsPartial({ item: inventoryItemPda(restaurant, sku), ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+
+const writeOffExpiredLots = (restaurant: TestRestaurant, sku: string) =>
+  program.methods
+    .restaurantWriteOffExpiredLots({ sku })
+    .accountsPartial({
+      signer: restaurant.owner.publicKey,
+      item: inventoryItemPda(restaurant, sku),
+      restaurant: restaurant.restaurant,
+      wasteLog: pda(Buffer.from("waste_log"), restaurant.restaurant.toBuffer()),
+    })
+    .signers([restaurant.owner])
+    .rpc();
+
+describe("inventory_lots", () => {
+  it("Consumes lots first-in-first-out when an order depletes stock", async () => {
+    const restaurant = await createRestaurant();
+    const milk = await addInventoryItem(restaurant, "milk", 0);
+    await receiveLot(restaurant, "milk", 2, 100);
+    await receiveLot(restaurant, "milk", 3, 200);
+
+    const latte = await addMenuItem(restaurant, "latte", 1_000_000, [[milk, 3]]);
+    await placeOrder(restaurant, await fundedKeypair(), [[latte, 1]]);
+
+    const item = await program.account.inventoryItem.fetch(milk);
+    expect(item.stock.toNumber()).to.equal(2);
+    expect(item.lots).to.have.length(1);
+    expect(item.lots[0].quantity.toNumber()).to.equal(2);
+    expect(item.lots[0].unitCost.toNumber()).to.equal(200);
+  });
+
+  it("Writes off expired lots into the waste log", async () => {
+    const restaurant = await createRestaurant();
+    const milk = await addInventoryItem(restaurant, "milk", 0);
+    await receiveLot(restaurant, "milk", 4, 150, now() + 2);
+    await receiveLot(restaurant, "milk", 1, 150);
+
+    // Wait for the cluster clock to pass the expiry
+    await sleep(5_000);
+    await writeOffExpiredLots(restaurant, "milk");
+
+    const item = await program.account.inventoryItem.fetch(milk);
+    expect(item.stock.toNumber()).to.equal(1);
+    expect(item.lots).to.have.length(1);
+    const wasteLog = await program.account.wasteLog.fetch(pda(Buffer.from("waste_log"), restaurant.restaurant.toBuffer()));
+    expect(wasteLog.costByReason[EXPIRED].toNumber()).to.equal(600);
+  });
+
+  it("Rejects a write-off when no lot has expired", async () => {
+    const restaurant = await createRestaurant();
+    await addInventoryItem(restaurant, "milk", 0);
+    await receiveLot(restaurant, "milk", 4, 150, now() + 3600);
+
+    await expectError(writeOffExpiredLots(restaurant, "milk"), "NothingExpired");
+  });
+
+  it("Rejects a lot that has already expired", async () => {
+    const restaurant = await createRestaurant();
+    await addInventoryItem(restaurant, "milk", 0);
+
+    await expectError(receiveLot(restaurant, "milk", 4, 150, now() - 60), "InvalidLot");
+  });
+
+  it("Rejects re-adding an inventory item that holds lots", async () => {
+    const restaurant = await createRestaurant();
+    await addInventoryItem(restaurant, "milk", 0);
+    await receiveLot(restaurant, "milk", 4, 150);
+
+    await expectError(addInventoryItem(restaurant, "milk", 0), "InventoryItemExists");
+  });
+});
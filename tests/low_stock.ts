@@ -0,0 +1,54 @@
+import { expect } from "chai";
+import {
+  addInventoryItem, addMenuItem, createRestaurant, expectError, fundedKeypair, inventoryItemPda, ownerAccounts,
+  placeOrder, program, readonly, TestMenuItem, TestRestaurant, writable,
+} from "./utils";
+
+const deactivateDependents = (restaurant: TestRestaurant, sku: string, items: TestMenuItem[]) =>
+  program.methods
+    .restaurantDeactivateDependentItems({ sku })
+    .accountsPartial({
+      signer: restaurant.owner.publicKey,
+      item: inventoryItemPda(restaurant, sku),
+      restaurant: restaurant.restaurant,
+    })
+    .remainingAccounts(items.flatMap((item) => [writable(item.menuItem), readonly(item.ingredientList)]))
+    .signers([restaurant.owner])
+    .rpc();
+
+describe("low_stock", () => {
+  it("Deactivates the menu items of an ingredient that ran out", async () => {
+    const restaurant = await createRestaurant();
+    const cheese = await addInventoryItem(restaurant, "cheese", 2, { reorderThreshold: 1, autoDeactivate: true });
+    const pizza = await addMenuItem(restaurant, "pizza", 1_000_000, [[cheese, 2]]);
+    const customer = await fundedKeypair();
+
+    await placeOrder(restaurant, customer, [[pizza, 1]]);
+    expect((await program.account.inventoryItem.fetch(cheese)).stock.toNumber()).to.equal(0);
+
+    await deactivateDependents(restaurant, "cheese", [pizza]);
+    expect((await program.account.menuItem.fetch(pizza.menuItem)).active).to.equal(false);
+  });
+
+  it("Rejects deactivating items while the stock still covers a unit", async () => {
+    const restaurant = await createRestaurant();
+    await addInventoryItem(restaurant, "cheese", 10, { reorderThreshold: 5, autoDeactivate: true });
+    const pizza = await addMenuItem(restaurant, "pizza", 1_000_000, [[inventoryItemPda(restaurant, "cheese"), 2]]);
+
+    await expectError(deactivateDependents(restaurant, "cheese", [pizza]), "NotDepleted");
+  });
+
+  it("Rejects migrating an inventory item that already has the current layout", async () => {
+    const restaurant = await createRestaurant();
+    const cheese = await addInventoryItem(restaurant, "cheese", 10);
+
+    await expectError(
+      program.methods
+        .restaurantMigrateInventoryItem({ sku: "cheese" })
+        .accountsPartial({ item: cheese, ...ownerAccounts(restaurant) })
+        .signers([restaurant.owner])
+        .rpc(),
+      "NotALegacyAccount",
+    );
+  });
+});
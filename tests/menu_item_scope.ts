@@ -0,0 +1,48 @@
+import { expect } from "chai";
+import {
+  addMenuItem, createRestaurant, expectError, fundedKeypair, pda, placeOrder, program, wallet,
+} from "./utils";
+
+describe("menu_item_scope", () => {
+  it("Lets two restaurants list the same SKU", async () => {
+    const diner = await createRestaurant();
+    const bistro = await createRestaurant();
+
+    const dinerSoup = await addMenuItem(diner, "soup", 700_000);
+    const bistroSoup = await addMenuItem(bistro, "soup", 900_000);
+
+    expect(dinerSoup.menuItem.equals(bistroSoup.menuItem)).to.equal(false);
+    expect((await program.account.menuItem.fetch(dinerSoup.menuItem)).restaurant.equals(diner.restaurant)).to.equal(true);
+    expect((await program.account.menuItem.fetch(bistroSoup.menuItem)).price.toNumber()).to.equal(900_000);
+  });
+
+  it("Rejects ordering another restaurant's menu item", async () => {
+    const diner = await createRestaurant();
+    const bistro = await createRestaurant();
+    const bistroSoup = await addMenuItem(bistro, "soup", 900_000);
+    const customer = await fundedKeypair();
+
+    await expectError(placeOrder(diner, customer, [[bistroSoup, 1]]), "InvalidMenuItem");
+  });
+
+  it("Rejects migrating a SKU without a legacy menu item", async () => {
+    const restaurant = await createRestaurant();
+    const legacyMenuItem = pda(Buffer.from("menu_item"), Buffer.from("no-legacy"));
+    const menuItem = pda(Buffer.from("menu_item"), restaurant.restaurant.toBuffer(), Buffer.from("no-legacy"));
+
+    await expectError(
+      program.methods
+        .protocolMigrateMenuItem({ sku: "no-legacy" })
+        .accountsPartial({
+          admin: wallet.publicKey,
+          restaurant: restaurant.restaurant,
+          legacyMenuItem,
+          legacyIngredientList: pda(Buffer.from("ingredient_list"), legacyMenuItem.toBuffer()),
+          menuItem,
+          ingredientList: pda(Buffer.from("ingredient_list"), menuItem.toBuffer()),
+        })
+        .rpc(),
+      "ConstraintOwner",
+    );
+  });
+});
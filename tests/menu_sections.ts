@@ -0,0 +1,89 @@
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addMenuItem, createRestaurant, expectError, ownerAccounts, pda, program, TestMenuItem, TestRestaurant,
+} from "./utils";
+
+const menuPda = (restaurant: TestRestaurant) => pda(Buffer.from("menu"), restaurant.restaurant.toBuffer());
+
+const sectionAccounts = (restaurant: TestRestaurant, item: TestMenuItem | null) => ({
+  menu: menuPda(restaurant),
+  menuItem: item?.menuItem ?? null,
+  ...ownerAccounts(restaurant),
+});
+
+const addEntry = (restaurant: TestRestaurant, item: TestMenuItem, section: string, position: number | null = null) =>
+  program.methods
+    .restaurantAddMenuEntry({ section, position })
+    .accountsPartial(sectionAccounts(restaurant, item))
+    .signers([restaurant.owner])
+    .rpc();
+
+const sectionItems = async (restaurant: TestRestaurant) =>
+  (await program.account.menu.fetch(menuPda(restaurant))).sections.map((section) => ({
+    name: section.name,
+    items: section.items.map((item: PublicKey) => item.toBase58()),
+  }));
+
+describe("menu_sections", () => {
+  it("Adds, moves and removes menu entries in display order", async () => {
+    const restaurant = await createRestaurant();
+    const soup = await addMenuItem(restaurant, "soup", 700_000);
+    const salad = await addMenuItem(restaurant, "salad", 600_000);
+    const cake = await addMenuItem(restaurant, "cake", 500_000);
+
+    await addEntry(restaurant, soup, "Starters");
+    await addEntry(restaurant, salad, "Starters", 0);
+    await addEntry(restaurant, cake, "Desserts");
+    expect(await sectionItems(restaurant)).to.deep.equal([
+      { name: "Starters", items: [salad.menuItem.toBase58(), soup.menuItem.toBase58()] },
+      { name: "Desserts", items: [cake.menuItem.toBase58()] },
+    ]);
+
+    await program.methods
+      .restaurantMoveMenuEntry({ section: null, from: 1, to: 0 })
+      .accountsPartial(sectionAccounts(restaurant, null))
+      .signers([restaurant.owner])
+      .rpc();
+    await program.methods
+      .restaurantRemoveMenuEntry({ section: "Desserts", menuItem: cake.menuItem })
+      .accountsPartial(sectionAccounts(restaurant, null))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect(await sectionItems(restaurant)).to.deep.equal([
+      { name: "Starters", items: [salad.menuItem.toBase58(), soup.menuItem.toBase58()] },
+    ]);
+  });
+
+  it("Rejects adding the same menu item twice to a section", async () => {
+    const restaurant = await createRestaurant();
+    const soup = await addMenuItem(restaurant, "soup", 700_000);
+    await addEntry(restaurant, soup, "Starters");
+
+    await expectError(addEntry(restaurant, soup, "Starters"), "DuplicateMenuEntry");
+  });
+
+  it("Rejects adding another restaurant's menu item", async () => {
+    const restaurant = await createRestaurant();
+    const other = await createRestaurant();
+    await addMenuItem(restaurant, "soup", 700_000);
+    const foreign = await addMenuItem(other, "soup", 700_000);
+
+    await expectError(addEntry(restaurant, foreign, "Starters"), "InvalidMenuItem");
+  });
+
+  it("Rejects migrating a menu that already has sections", async () => {
+    const restaurant = await createRestaurant();
+    await addMenuItem(restaurant, "soup", 700_000);
+
+    await expectError(
+      program.methods
+        .restaurantMigrateMenu()
+        .accountsPartial({ menu: menuPda(restaurant), ...ownerAccounts(restaurant) })
+        .signers([restaurant.owner])
+        .rpc(),
+      "NotALegacyAccount",
+    );
+  });
+});
@@ -0,0 +1,93 @@
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addInventoryItem, addMenuItem, bn, createRestaurant, expectError, fundedKeypair, lineAccounts, ownerAccounts, pda,
+  placeOrder, program, readonly, TestMenuItem, TestRestaurant, writable,
+} from "./utils";
+
+const modifierGroupPda = (item: TestMenuItem, name: string) =>
+  pda(Buffer.from("modifier_group"), item.menuItem.toBuffer(), Buffer.from(name));
+
+const groupAccounts = (restaurant: TestRestaurant, item: TestMenuItem, name: string) => ({
+  menuItem: item.menuItem,
+  modifierGroup: modifierGroupPda(item, name),
+  ...ownerAccounts(restaurant),
+});
+
+describe("modifier_groups", () => {
+  let restaurant: TestRestaurant;
+  let coffee: TestMenuItem;
+  let milk: PublicKey;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    milk = await addInventoryItem(restaurant, "milk", 100);
+    coffee = await addMenuItem(restaurant, "coffee", 1_000_000);
+
+    await program.methods
+      .restaurantAddModifierGroup({
+        sku: coffee.sku,
+        name: "Size",
+        minSelections: 1,
+        maxSelections: 1,
+        options: [
+          { name: "Small", priceDelta: bn(0), ingredients: [] },
+          { name: "Latte", priceDelta: bn(500_000), ingredients: [[milk, bn(2)]] },
+        ],
+      })
+      .accountsPartial(groupAccounts(restaurant, coffee, "Size"))
+      .signers([restaurant.owner])
+      .rpc();
+  });
+
+  it("Prices the selected option and depletes its ingredients", async () => {
+    const customer = await fundedKeypair();
+    const { order } = await placeOrder(restaurant, customer, [[coffee, 1]], {
+      total: 1_500_000,
+      modifiers: [[{ group: 0, option: 1 }]],
+      remainingAccounts: [...lineAccounts(coffee), readonly(modifierGroupPda(coffee, "Size")), writable(milk)],
+    });
+
+    expect((await program.account.customerOrder.fetch(order)).total.toNumber()).to.equal(1_500_000);
+    expect((await program.account.inventoryItem.fetch(milk)).stock.toNumber()).to.equal(98);
+  });
+
+  it("Rejects an order line missing a required selection", async () => {
+    const customer = await fundedKeypair();
+
+    await expectError(
+      placeOrder(restaurant, customer, [[coffee, 1]], {
+        remainingAccounts: [...lineAccounts(coffee), readonly(modifierGroupPda(coffee, "Size"))],
+      }),
+      "InvalidModifierSelection",
+    );
+  });
+
+  it("Rejects a group whose bounds exceed its options", async () => {
+    await expectError(
+      program.methods
+        .restaurantAddModifierGroup({
+          sku: coffee.sku,
+          name: "Syrup",
+          minSelections: 0,
+          maxSelections: 2,
+          options: [{ name: "Vanilla", priceDelta: bn(100_000), ingredients: [] }],
+        })
+        .accountsPartial(groupAccounts(restaurant, coffee, "Syrup"))
+        .signers([restaurant.owner])
+        .rpc(),
+      "InvalidModifierGroup",
+    );
+  });
+
+  it("Removes the group and closes its account", async () => {
+    await program.methods
+      .restaurantRemoveModifierGroup({ sku: coffee.sku, name: "Size" })
+      .accountsPartial(groupAccounts(restaurant, coffee, "Size"))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect((await program.account.menuItem.fetch(coffee.menuItem)).modifierGroups).to.have.length(0);
+    expect(await program.account.modifierGroup.fetchNullable(modifierGroupPda(coffee, "Size"))).to.equal(null);
+  });
+});
@@ -0,0 +1,76 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { expectError, fundedKeypair, initProtocol, pda, program, wallet } from "./utils";
+
+describe("payment_signers", () => {
+  const paymentConfig = pda(Buffer.from("payment_config"));
+  const signer = Keypair.generate().publicKey;
+  const rotated = Keypair.generate().publicKey;
+
+  before(async () => {
+    await initProtocol();
+  });
+
+  it("Admin adds, rotates and revokes a payment signer", async () => {
+    await program.methods
+      .protocolAddPaymentSigner({ key: signer, label: "stripe" })
+      .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+      .rpc();
+
+    let config = await program.account.paymentConfig.fetch(paymentConfig);
+    let entry = config.signers.find((s) => s.key.equals(signer));
+    expect(entry.label).to.equal("stripe");
+    expect(entry.active).to.equal(true);
+
+    await program.methods
+      .protocolRotatePaymentSigner({ key: signer, newKey: rotated })
+      .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+      .rpc();
+
+    config = await program.account.paymentConfig.fetch(paymentConfig);
+    expect(config.signers.some((s) => s.key.equals(signer))).to.equal(false);
+    entry = config.signers.find((s) => s.key.equals(rotated));
+    expect(entry.label).to.equal("stripe");
+
+    await program.methods
+      .protocolRevokePaymentSigner({ key: rotated })
+      .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+      .rpc();
+
+    config = await program.account.paymentConfig.fetch(paymentConfig);
+    expect(config.signers.find((s) => s.key.equals(rotated)).active).to.equal(false);
+  });
+
+  it("Rejects a signer that is already registered", async () => {
+    await expectError(
+      program.methods
+        .protocolAddPaymentSigner({ key: rotated, label: "stripe" })
+        .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+        .rpc(),
+      "PaymentSignerExists",
+    );
+  });
+
+  it("Rejects rotating an unknown signer", async () => {
+    await expectError(
+      program.methods
+        .protocolRotatePaymentSigner({ key: Keypair.generate().publicKey, newKey: Keypair.generate().publicKey })
+        .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+        .rpc(),
+      "PaymentSignerNotFound",
+    );
+  });
+
+  it("Rejects payment signer changes from anyone but the protocol admin", async () => {
+    const intruder = await fundedKeypair();
+
+    await expectError(
+      program.methods
+        .protocolAddPaymentSigner({ key: intruder.publicKey, label: "intruder" })
+        .accountsPartial({ owner: intruder.publicKey, paymentConfig })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized",
+    );
+  });
+});
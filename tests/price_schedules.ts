@@ -0,0 +1,97 @@
+import { expect } from "chai";
+import {
+  addMenuItem, createRestaurant, expectError, fundedKeypair, lineAccounts, ownerAccounts, pda, placeOrder, program,
+  readonly, TestMenuItem, TestRestaurant,
+} from "./utils";
+
+const EVERY_DAY = 0b1111111;
+
+const priceSchedulePda = (item: TestMenuItem) => pda(Buffer.from("price_schedule"), item.menuItem.toBuffer());
+
+async function setHappyHour(restaurant: TestRestaurant, item: TestMenuItem, windows = [
+  { days: EVERY_DAY, startMinute: 0, endMinute: 24 * 60, adjustment: { percentOff: { bps: 5000 } } },
+]) {
+  await program.methods
+    .restaurantSetPriceSchedule({ sku: item.sku, windows })
+    .accountsPartial({ menuItem: item.menuItem, priceSchedule: priceSchedulePda(item), ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+}
+
+describe("price_schedules", () => {
+  it("Prices an order at the happy-hour price while the window is open", async () => {
+    const restaurant = await createRestaurant();
+    const beer = await addMenuItem(restaurant, "beer", 1_000_000);
+    await setHappyHour(restaurant, beer);
+
+    const customer = await fundedKeypair();
+    const { order } = await placeOrder(restaurant, customer, [[beer, 1]], {
+      total: 500_000,
+      remainingAccounts: [...lineAccounts(beer), readonly(priceSchedulePda(beer))],
+    });
+
+    expect((await program.account.customerOrder.fetch(order)).total.toNumber()).to.equal(500_000);
+  });
+
+  it("Goes back to the static price once the schedule is removed", async () => {
+    const restaurant = await createRestaurant();
+    const beer = await addMenuItem(restaurant, "beer", 1_000_000);
+    await setHappyHour(restaurant, beer);
+
+    await program.methods
+      .restaurantRemovePriceSchedule({ sku: beer.sku })
+      .accountsPartial({ menuItem: beer.menuItem, priceSchedule: priceSchedulePda(beer), ...ownerAccounts(restaurant) })
+      .signers([restaurant.owner])
+      .rpc();
+    expect((await program.account.menuItem.fetch(beer.menuItem)).priceSchedule).to.equal(null);
+
+    const customer = await fundedKeypair();
+    const { order } = await placeOrder(restaurant, customer, [[beer, 1]]);
+    expect((await program.account.customerOrder.fetch(order)).total.toNumber()).to.equal(1_000_000);
+  });
+
+  it("Rejects an order that leaves out the item's price schedule", async () => {
+    const restaurant = await createRestaurant();
+    const beer = await addMenuItem(restaurant, "beer", 1_000_000);
+    await setHappyHour(restaurant, beer);
+    const customer = await fundedKeypair();
+
+    await expectError(placeOrder(restaurant, customer, [[beer, 1]], { total: 500_000 }), "MissingPriceSchedule");
+  });
+
+  it("Rejects a window that ends before it starts", async () => {
+    const restaurant = await createRestaurant();
+    const beer = await addMenuItem(restaurant, "beer", 1_000_000);
+
+    await expectError(
+      setHappyHour(restaurant, beer, [
+        { days: EVERY_DAY, startMinute: 18 * 60, endMinute: 17 * 60, adjustment: { percentOff: { bps: 5000 } } },
+      ]),
+      "InvalidPriceWindow",
+    );
+  });
+
+  it("Updates the restaurant's UTC offset", async () => {
+    const restaurant = await createRestaurant();
+    await program.methods
+      .restaurantUpdateUtcOffset(120)
+      .accountsPartial(ownerAccounts(restaurant))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect((await program.account.restaurant.fetch(restaurant.restaurant)).utcOffsetMinutes).to.equal(120);
+  });
+
+  it("Rejects a UTC offset outside -12:00 to +14:00", async () => {
+    const restaurant = await createRestaurant();
+
+    await expectError(
+      program.methods
+        .restaurantUpdateUtcOffset(15 * 60)
+        .accountsPartial(ownerAccounts(restaurant))
+        .signers([restaurant.owner])
+        .rpc(),
+      "InvalidUtcOffset",
+    );
+  });
+});
@@ -0,0 +1,89 @@
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addMenuItem, bn, createRestaurant, expectError, fundedKeypair, now, ownerAccounts, pda, placeOrder, program,
+  TestRestaurant,
+} from "./utils";
+
+const promotionPda = (restaurant: TestRestaurant, code: string) =>
+  pda(Buffer.from("promotion"), restaurant.restaurant.toBuffer(), Buffer.from(code));
+
+const promotionAccounts = (promotion: PublicKey, customer: PublicKey) => ({
+  promotion,
+  promotionRedemption: pda(Buffer.from("promotion_redemption"), promotion.toBuffer(), customer.toBuffer()),
+});
+
+async function createPromotion(restaurant: TestRestaurant, code: string) {
+  await program.methods
+    .restaurantCreatePromotion({
+      code,
+      rule: { percentOff: { bps: 2000 } },
+      category: null,
+      startsAt: bn(now() - 60),
+      endsAt: bn(now() + 3600),
+      maxRedemptions: null,
+      perCustomerLimit: 1,
+    })
+    .accountsPartial({ promotion: promotionPda(restaurant, code), ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+
+  return promotionPda(restaurant, code);
+}
+
+describe("promotions", () => {
+  it("Applies a percent-off promo code to the order", async () => {
+    const restaurant = await createRestaurant();
+    const promotion = await createPromotion(restaurant, "SAVE20");
+    const burger = await addMenuItem(restaurant, "burger", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { order } = await placeOrder(restaurant, customer, [[burger, 1]], {
+      promoCode: "SAVE20",
+      accounts: promotionAccounts(promotion, customer.publicKey),
+    });
+
+    const placed = await program.account.customerOrder.fetch(order);
+    expect(placed.discount.toNumber()).to.equal(200_000);
+    expect(placed.total.toNumber()).to.equal(800_000);
+    expect(placed.promotion.equals(promotion)).to.equal(true);
+    expect((await program.account.promotion.fetch(promotion)).redemptions).to.equal(1);
+  });
+
+  it("Rejects a promo code once the customer reached its limit", async () => {
+    const restaurant = await createRestaurant();
+    const promotion = await createPromotion(restaurant, "ONCE");
+    const burger = await addMenuItem(restaurant, "burger", 1_000_000);
+    const customer = await fundedKeypair();
+    const accounts = promotionAccounts(promotion, customer.publicKey);
+
+    await placeOrder(restaurant, customer, [[burger, 1]], { promoCode: "ONCE", accounts });
+
+    await expectError(
+      placeOrder(restaurant, customer, [[burger, 1]], { promoCode: "ONCE", accounts }),
+      "CustomerLimitReached",
+    );
+  });
+
+  it("Rejects a promo code that was toggled off", async () => {
+    const restaurant = await createRestaurant();
+    const promotion = await createPromotion(restaurant, "PAUSED");
+    await program.methods
+      .restaurantTogglePromotion({ code: "PAUSED" })
+      .accountsPartial({ promotion, ...ownerAccounts(restaurant) })
+      .signers([restaurant.owner])
+      .rpc();
+    expect((await program.account.promotion.fetch(promotion)).active).to.equal(false);
+
+    const burger = await addMenuItem(restaurant, "burger", 1_000_000);
+    const customer = await fundedKeypair();
+
+    await expectError(
+      placeOrder(restaurant, customer, [[burger, 1]], {
+        promoCode: "PAUSED",
+        accounts: promotionAccounts(promotion, customer.publicKey),
+      }),
+      "PromotionInactive",
+    );
+  });
+});
@@ -0,0 +1,116 @@
+import { expect } from "chai";
+import {
+  addEmployee, addInventoryItem, addMenuItem, balance, bn, completeOrder, createRestaurant, customerPda, EmployeeType,
+  expectError, fundedKeypair, orderPda, placeOrder, program,
+} from "./utils";
+
+describe("refund_order", () => {
+  it("Manager refunds a completed native SOL order to the customer", async () => {
+    const restaurant = await createRestaurant();
+    const manager = await addEmployee(restaurant, EmployeeType.Manager);
+    const flour = await addInventoryItem(restaurant, "flour", 100);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000, [[flour, 1]]);
+    const customer = await fundedKeypair();
+
+    const { orderId, order } = await placeOrder(restaurant, customer, [[bread, 2]]);
+    await completeOrder(restaurant, manager, orderId);
+
+    const paid = (await program.account.customerOrder.fetch(order)).paid.toNumber();
+    const customerAccount = customerPda(restaurant, customer.publicKey);
+    expect((await program.account.customer.fetch(customerAccount)).rewardPoints.toNumber()).to.be.greaterThan(0);
+    const before = await balance(customer.publicKey);
+
+    await program.methods
+      .restaurantRefundOrder({ orderId: bn(orderId), amount: bn(paid), shareIndex: null })
+      .accountsPartial({
+        order,
+        signer: manager.keypair.publicKey,
+        customer: customerPda(restaurant, customer.publicKey),
+        restaurant: restaurant.restaurant,
+        employee: manager.employee,
+        currency: null,
+        restaurantAta: null,
+        customerAta: null,
+        customerWallet: customer.publicKey,
+        tokenProgram: null,
+      })
+      .signers([manager.keypair])
+      .rpc();
+
+    const refunded = await program.account.customerOrder.fetch(order);
+    expect(refunded.refunded.toNumber()).to.equal(paid);
+    expect(refunded.status).to.have.property("refunded");
+    expect(await balance(customer.publicKey)).to.equal(before + paid);
+    // The reward points earned by the order are taken back with the full refund
+    expect((await program.account.customer.fetch(customerAccount)).rewardPoints.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a refund larger than what was paid", async () => {
+    const restaurant = await createRestaurant();
+    const manager = await addEmployee(restaurant, EmployeeType.Manager);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    await completeOrder(restaurant, manager, orderId);
+
+    await expectError(
+      program.methods
+        .restaurantRefundOrder({ orderId: bn(orderId), amount: bn(2_000_000_000), shareIndex: null })
+        .accountsPartial({
+          order: orderPda(restaurant, orderId),
+          signer: manager.keypair.publicKey,
+          customer: customerPda(restaurant, customer.publicKey),
+          restaurant: restaurant.restaurant,
+          employee: manager.employee,
+          currency: null,
+          restaurantAta: null,
+          customerAta: null,
+          customerWallet: customer.publicKey,
+          tokenProgram: null,
+        })
+        .signers([manager.keypair])
+        .rpc(),
+      "InvalidRefundAmount",
+    );
+  });
+
+  it("Rejects a refund signed by a team member", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    await completeOrder(restaurant, member, orderId);
+
+    await expectError(
+      program.methods
+        .restaurantRefundOrder({ orderId: bn(orderId), amount: bn(1), shareIndex: null })
+        .accountsPartial({
+          order: orderPda(restaurant, orderId),
+          signer: member.keypair.publicKey,
+          customer: customerPda(restaurant, customer.publicKey),
+          restaurant: restaurant.restaurant,
+          employee: member.employee,
+          currency: null,
+          restaurantAta: null,
+          customerAta: null,
+          customerWallet: customer.publicKey,
+          tokenProgram: null,
+        })
+        .signers([member.keypair])
+        .rpc(),
+      "Unauthorized",
+    );
+  });
+
+  it("Rejects an order naming another wallet as its customer, which would receive the refunds", async () => {
+    const restaurant = await createRestaurant();
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+    const other = await fundedKeypair();
+
+    await expectError(placeOrder(restaurant, customer, [[bread, 1]], { customer: other.publicKey }), "CustomerMismatch");
+  });
+});
@@ -0,0 +1,76 @@
+import { expect } from "chai";
+import {
+  addMenuItem, balance, createRestaurant, expectError, ownerAccounts, pda, program, TestMenuItem, TestRestaurant,
+  writable,
+} from "./utils";
+
+const menuPda = (restaurant: TestRestaurant) => pda(Buffer.from("menu"), restaurant.restaurant.toBuffer());
+
+const toggleMenuItem = (restaurant: TestRestaurant, item: TestMenuItem) =>
+  program.methods
+    .restaurantToggleMenuItem({ sku: item.sku })
+    .accountsPartial({ item: item.menuItem, menu: menuPda(restaurant), ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+
+const removeMenuItem = (restaurant: TestRestaurant, item: TestMenuItem) =>
+  program.methods
+    .restaurantRemoveMenuItem({ sku: item.sku })
+    .accountsPartial({
+      menuItem: item.menuItem,
+      ingredientList: item.ingredientList,
+      menu: menuPda(restaurant),
+      ...ownerAccounts(restaurant),
+    })
+    .signers([restaurant.owner])
+    .rpc();
+
+describe("remove_menu_item", () => {
+  it("Closes a deactivated menu item and its ingredient list", async () => {
+    const restaurant = await createRestaurant();
+    const soup = await addMenuItem(restaurant, "soup", 700_000);
+    await toggleMenuItem(restaurant, soup);
+
+    const rent = (await balance(soup.menuItem)) + (await balance(soup.ingredientList));
+    const ownerBefore = await balance(restaurant.owner.publicKey);
+    await removeMenuItem(restaurant, soup);
+
+    expect(await program.account.menuItem.fetchNullable(soup.menuItem)).to.equal(null);
+    expect(await program.account.ingredientList.fetchNullable(soup.ingredientList)).to.equal(null);
+    // The owner also paid the transaction fee
+    expect(await balance(restaurant.owner.publicKey)).to.be.greaterThan(ownerBefore + rent - 10_000);
+  });
+
+  it("Rejects adding an existing menu item again, which would reset its references", async () => {
+    const restaurant = await createRestaurant();
+    await addMenuItem(restaurant, "soup", 700_000);
+
+    await expectError(addMenuItem(restaurant, "soup", 500_000), "MenuItemExists");
+  });
+
+  it("Rejects removing an active menu item", async () => {
+    const restaurant = await createRestaurant();
+    const soup = await addMenuItem(restaurant, "soup", 700_000);
+
+    await expectError(removeMenuItem(restaurant, soup), "MenuItemActive");
+  });
+
+  it("Rejects removing a menu item used by a combo", async () => {
+    const restaurant = await createRestaurant();
+    const soup = await addMenuItem(restaurant, "soup", 700_000, [], 1);
+    const lunch = await addMenuItem(restaurant, "lunch", 1_000_000, [], 0);
+    await program.methods
+      .restaurantSetCombo({ sku: lunch.sku, slots: [{ menuItem: soup.menuItem, quantity: 1, substitutes: [] }] })
+      .accountsPartial({
+        menuItem: lunch.menuItem,
+        combo: pda(Buffer.from("combo"), lunch.menuItem.toBuffer()),
+        ...ownerAccounts(restaurant),
+      })
+      .remainingAccounts([writable(soup.menuItem)])
+      .signers([restaurant.owner])
+      .rpc();
+    await toggleMenuItem(restaurant, soup);
+
+    await expectError(removeMenuItem(restaurant, soup), "MenuItemInCombo");
+  });
+});
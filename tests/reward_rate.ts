@@ -0,0 +1,52 @@
+import { expect } from "chai";
+import {
+  addMenuItem, bn, createRestaurant, customerPda, expectError, fundedKeypair, ownerAccounts, placeOrder, program,
+} from "./utils";
+
+describe("reward_rate", () => {
+  it("Accrues points on fractional SOL amounts at the updated rate", async () => {
+    const restaurant = await createRestaurant();
+    await program.methods
+      .restaurantUpdateRewardRate(bn(100))
+      .accountsPartial(ownerAccounts(restaurant))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect((await program.account.restaurant.fetch(restaurant.restaurant)).rewardPointsPerToken.toNumber()).to.equal(100);
+
+    // 0.25 SOL at 100 points per SOL
+    const coffee = await addMenuItem(restaurant, "coffee", 250_000_000);
+    const customer = await fundedKeypair();
+    await placeOrder(restaurant, customer, [[coffee, 1]]);
+
+    const account = await program.account.customer.fetch(customerPda(restaurant, customer.publicKey));
+    expect(account.rewardPoints.toNumber()).to.equal(25);
+  });
+
+  it("Rejects a reward rate update for another admin's restaurant", async () => {
+    const restaurant = await createRestaurant();
+    const other = await createRestaurant();
+
+    await expectError(
+      program.methods
+        .restaurantUpdateRewardRate(bn(100))
+        .accountsPartial({ ...ownerAccounts(other), restaurant: restaurant.restaurant })
+        .signers([other.owner])
+        .rpc(),
+      "ConstraintSeeds",
+    );
+  });
+
+  it("Rejects migrating a restaurant that already has the current layout", async () => {
+    const restaurant = await createRestaurant();
+
+    await expectError(
+      program.methods
+        .restaurantMigrate()
+        .accountsPartial(ownerAccounts(restaurant))
+        .signers([restaurant.owner])
+        .rpc(),
+      "NotALegacyAccount",
+    );
+  });
+});
@@ -0,0 +1,72 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addEmployee, addMenuItem, balance, bn, createRestaurant, customerPda, EmployeeType, expectError, fundedKeypair,
+  orderPda, placeOrder, program, Status, TestRestaurant, updateOrder,
+} from "./utils";
+
+const payShare = (restaurant: TestRestaurant, payer: Keypair, orderId: number, shareIndex: number) =>
+  program.methods
+    .customerPayOrderShare({ orderId: bn(orderId), shareIndex, customerName: "Guest" })
+    .accountsPartial({
+      order: orderPda(restaurant, orderId),
+      customer: customerPda(restaurant, payer.publicKey),
+      signer: payer.publicKey,
+      restaurant: restaurant.restaurant,
+      currency: null,
+      signerAta: null,
+      restaurantAta: null,
+      escrow: null,
+      tokenProgram: null,
+    })
+    .signers([payer])
+    .rpc();
+
+describe("split_bill", () => {
+  it("Settles a split bill once every share is paid by a different wallet", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const pizza = await addMenuItem(restaurant, "pizza", 1_000_000);
+    const host = await fundedKeypair();
+    const guest = await fundedKeypair();
+
+    const restaurantBefore = await balance(restaurant.restaurant);
+    const { orderId, order } = await placeOrder(restaurant, host, [[pizza, 1]], { splitShares: [600_000, 400_000] });
+    expect((await program.account.customerOrder.fetch(order)).paid.toNumber()).to.equal(0);
+
+    await payShare(restaurant, host, orderId, 0);
+    await payShare(restaurant, guest, orderId, 1);
+
+    const settled = await program.account.customerOrder.fetch(order);
+    expect(settled.paid.toNumber()).to.equal(1_000_000);
+    expect(settled.shares[0].payer.equals(host.publicKey)).to.equal(true);
+    expect(settled.shares[1].payer.equals(guest.publicKey)).to.equal(true);
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore + 1_000_000);
+
+    await updateOrder(restaurant, member, orderId, Status.Accepted);
+  });
+
+  it("Rejects accepting a split bill before every share is paid", async () => {
+    const restaurant = await createRestaurant();
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const pizza = await addMenuItem(restaurant, "pizza", 1_000_000);
+    const host = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, host, [[pizza, 1]], { splitShares: [600_000, 400_000] });
+    await payShare(restaurant, host, orderId, 0);
+
+    await expectError(updateOrder(restaurant, member, orderId, Status.Accepted), "OrderNotSettled");
+  });
+
+  it("Rejects paying a share that is already paid", async () => {
+    const restaurant = await createRestaurant();
+    const pizza = await addMenuItem(restaurant, "pizza", 1_000_000);
+    const host = await fundedKeypair();
+    const guest = await fundedKeypair();
+
+    const { orderId } = await placeOrder(restaurant, host, [[pizza, 1]], { splitShares: [600_000, 400_000] });
+    await payShare(restaurant, host, orderId, 0);
+
+    await expectError(payShare(restaurant, guest, orderId, 0), "ShareAlreadyPaid");
+  });
+});
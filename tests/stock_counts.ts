@@ -0,0 +1,133 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addEmployee, addInventoryItem, bn, createRestaurant, EmployeeType, expectError, inventoryItemPda, ownerAccounts, pda,
+  program, randomId, TestRestaurant, u64, writable,
+} from "./utils";
+
+type TestEmployee = { keypair: Keypair; employee: PublicKey };
+
+const stockCountPda = (restaurant: TestRestaurant, id: number) =>
+  pda(Buffer.from("stock_count"), restaurant.restaurant.toBuffer(), u64(id));
+const recordPda = (restaurant: TestRestaurant, id: number, item: PublicKey) =>
+  pda(Buffer.from("stock_count_record"), stockCountPda(restaurant, id).toBuffer(), item.toBuffer());
+
+const openStockCount = (restaurant: TestRestaurant, employee: TestEmployee, id: number) =>
+  program.methods
+    .restaurantOpenStockCount({ id: bn(id) })
+    .accountsPartial({
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      stockCount: stockCountPda(restaurant, id),
+    })
+    .signers([employee.keypair])
+    .rpc();
+
+const submitStockCount = (restaurant: TestRestaurant, employee: TestEmployee, id: number, sku: string, counted: number) =>
+  program.methods
+    .restaurantSubmitStockCount({ id: bn(id), sku, counted: bn(counted) })
+    .accountsPartial({
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      stockCount: stockCountPda(restaurant, id),
+      item: inventoryItemPda(restaurant, sku),
+      record: recordPda(restaurant, id, inventoryItemPda(restaurant, sku)),
+    })
+    .signers([employee.keypair])
+    .rpc();
+
+const finalizeStockCount = (restaurant: TestRestaurant, employee: TestEmployee, id: number, items: PublicKey[]) =>
+  program.methods
+    .restaurantFinalizeStockCount({ id: bn(id) })
+    .accountsPartial({
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      stockCount: stockCountPda(restaurant, id),
+    })
+    .remainingAccounts(items.flatMap((item) => [writable(recordPda(restaurant, id, item)), writable(item)]))
+    .signers([employee.keypair])
+    .rpc();
+
+describe("stock_counts", () => {
+  let restaurant: TestRestaurant;
+  let manager: TestEmployee;
+  let member: TestEmployee;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    manager = await addEmployee(restaurant, EmployeeType.Manager);
+    member = await addEmployee(restaurant, EmployeeType.TeamMember);
+  });
+
+  it("Applies the counted variance once the manager finalizes the session", async () => {
+    const rice = await addInventoryItem(restaurant, "rice", 10);
+    const id = randomId();
+
+    await openStockCount(restaurant, manager, id);
+    await submitStockCount(restaurant, member, id, "rice", 7);
+
+    const record = await program.account.stockCountRecord.fetch(recordPda(restaurant, id, rice));
+    expect(record.expected.toNumber()).to.equal(10);
+    expect(record.variance.toNumber()).to.equal(-3);
+    expect((await program.account.inventoryItem.fetch(rice)).stock.toNumber()).to.equal(10);
+
+    await finalizeStockCount(restaurant, manager, id, [rice]);
+
+    expect((await program.account.inventoryItem.fetch(rice)).stock.toNumber()).to.equal(7);
+    expect((await program.account.stockCountRecord.fetch(recordPda(restaurant, id, rice))).applied).to.equal(true);
+    expect((await program.account.stockCount.fetch(stockCountPda(restaurant, id))).status).to.have.property("finalized");
+  });
+
+  it("Rejects a session opened by a team member", async () => {
+    await expectError(openStockCount(restaurant, member, randomId()), "UnauthorizedStockCount");
+  });
+
+  it("Rejects a finalization signed by a team member", async () => {
+    await addInventoryItem(restaurant, "beans", 10);
+    const id = randomId();
+    await openStockCount(restaurant, manager, id);
+    await submitStockCount(restaurant, member, id, "beans", 9);
+
+    await expectError(
+      finalizeStockCount(restaurant, member, id, [inventoryItemPda(restaurant, "beans")]),
+      "UnauthorizedStockCount",
+    );
+  });
+
+  it("Rejects counts submitted after the session was finalized", async () => {
+    await addInventoryItem(restaurant, "oil", 10);
+    const id = randomId();
+    await openStockCount(restaurant, manager, id);
+    await submitStockCount(restaurant, member, id, "oil", 8);
+    await finalizeStockCount(restaurant, manager, id, [inventoryItemPda(restaurant, "oil")]);
+
+    await expectError(submitStockCount(restaurant, member, id, "oil", 9), "StockCountClosed");
+  });
+
+  it("Keeps the stock when an inventory item is updated, counts are the way to correct it", async () => {
+    const flour = await addInventoryItem(restaurant, "flour", 10);
+
+    await program.methods
+      .restaurantAddInventoryItem({
+        sku: "flour",
+        category: 2,
+        name: "Inventory flour",
+        price: bn(200),
+        stock: bn(3),
+        reorderThreshold: bn(0),
+        parLevel: bn(0),
+        autoDeactivate: false,
+        initialized: true,
+      })
+      .accountsPartial({ item: flour, ...ownerAccounts(restaurant) })
+      .signers([restaurant.owner])
+      .rpc();
+
+    const item = await program.account.inventoryItem.fetch(flour);
+    expect(item.price.toNumber()).to.equal(200);
+    expect(item.stock.toNumber()).to.equal(10);
+  });
+});
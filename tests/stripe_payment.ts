@@ -0,0 +1,87 @@
+import { Ed25519Program, Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addMenuItem, createRestaurant, expectError, fundedKeypair, now, orderPda, pda, placeOrder, program, randomId,
+  TestRestaurant, u64, wallet,
+} from "./utils";
+
+// Borsh layout of StripePaymentMessage
+const stripeMessage = (orderId: number, restaurant: PublicKey, customer: PublicKey, amount: number, expiresAt: number) => {
+  const expires = Buffer.alloc(8);
+  expires.writeBigInt64LE(BigInt(expiresAt));
+  return Buffer.concat([u64(orderId), restaurant.toBuffer(), customer.toBuffer(), u64(amount), u64(randomId()), expires]);
+};
+
+const signPayment = (signer: Keypair, message: Buffer) =>
+  Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message });
+
+describe("stripe_payment", () => {
+  const paymentConfig = pda(Buffer.from("payment_config"));
+  const stripe = Keypair.generate();
+
+  let restaurant: TestRestaurant;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    await program.methods
+      .protocolAddPaymentSigner({ key: stripe.publicKey, label: "stripe" })
+      .accountsPartial({ owner: wallet.publicKey, paymentConfig })
+      .rpc();
+  });
+
+  it("Accepts a signed Stripe payment and consumes its receipt", async () => {
+    const bread = await addMenuItem(restaurant, "stripe-bread", 1_000_000);
+    const customer = await fundedKeypair();
+    const orderId = randomId();
+    const order = orderPda(restaurant, orderId);
+    const receipt = pda(Buffer.from("receipt"), order.toBuffer());
+
+    await placeOrder(restaurant, customer, [[bread, 1]], {
+      orderId,
+      accounts: { receipt, paymentConfig },
+      preInstructions: [
+        signPayment(stripe, stripeMessage(orderId, restaurant.restaurant, customer.publicKey, 1_000_000, now() + 600)),
+      ],
+    });
+
+    const consumed = await program.account.paymentReceipt.fetch(receipt);
+    expect(consumed.order.equals(order)).to.equal(true);
+    expect(consumed.amount.toNumber()).to.equal(1_000_000);
+    expect((await program.account.customerOrder.fetch(order)).paid.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a signed payment replayed for another order", async () => {
+    const bread = await addMenuItem(restaurant, "stripe-toast", 1_000_000);
+    const customer = await fundedKeypair();
+    const signedFor = randomId();
+    const orderId = randomId();
+
+    await expectError(
+      placeOrder(restaurant, customer, [[bread, 1]], {
+        orderId,
+        accounts: { receipt: pda(Buffer.from("receipt"), orderPda(restaurant, orderId).toBuffer()), paymentConfig },
+        preInstructions: [
+          signPayment(stripe, stripeMessage(signedFor, restaurant.restaurant, customer.publicKey, 1_000_000, now() + 600)),
+        ],
+      }),
+      "PaymentMessageMismatch",
+    );
+  });
+
+  it("Rejects a payment signed by an unregistered key", async () => {
+    const bread = await addMenuItem(restaurant, "stripe-bagel", 1_000_000);
+    const customer = await fundedKeypair();
+    const orderId = randomId();
+
+    await expectError(
+      placeOrder(restaurant, customer, [[bread, 1]], {
+        orderId,
+        accounts: { receipt: pda(Buffer.from("receipt"), orderPda(restaurant, orderId).toBuffer()), paymentConfig },
+        preInstructions: [
+          signPayment(Keypair.generate(), stripeMessage(orderId, restaurant.restaurant, customer.publicKey, 1_000_000, now() + 600)),
+        ],
+      }),
+      "SignatureAuthorityMismatch",
+    );
+  });
+});
@@ -0,0 +1,113 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addInventoryItem, addMenuItem, balance, bn, createRestaurant, expectError, fundedKeypair, ownerAccounts, pda,
+  placeOrder, program, randomId, TestRestaurant, u64, writable,
+} from "./utils";
+
+const supplierPda = (restaurant: TestRestaurant, id: number) =>
+  pda(Buffer.from("supplier"), restaurant.restaurant.toBuffer(), u64(id));
+const purchaseOrderPda = (restaurant: TestRestaurant, id: number) =>
+  pda(Buffer.from("purchase_order"), restaurant.restaurant.toBuffer(), u64(id));
+
+const receivePurchaseOrder = (restaurant: TestRestaurant, id: number, quantity: number, item: PublicKey) =>
+  program.methods
+    .restaurantReceivePurchaseOrder({ id: bn(id), lines: [{ quantity: bn(quantity), expiresAt: null }] })
+    .accountsPartial({
+      signer: restaurant.owner.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: null,
+      purchaseOrder: purchaseOrderPda(restaurant, id),
+    })
+    .remainingAccounts([writable(item)])
+    .signers([restaurant.owner])
+    .rpc();
+
+const payPurchaseOrder = (restaurant: TestRestaurant, id: number, supplierId: number, destination: PublicKey) =>
+  program.methods
+    .restaurantPayPurchaseOrder({ id: bn(id) })
+    .accountsPartial({
+      purchaseOrder: purchaseOrderPda(restaurant, id),
+      supplier: supplierPda(restaurant, supplierId),
+      ...ownerAccounts(restaurant),
+      currency: null,
+      restaurantAta: null,
+      tokenProgram: null,
+    })
+    .remainingAccounts([writable(destination)])
+    .signers([restaurant.owner])
+    .rpc();
+
+describe("suppliers", () => {
+  let restaurant: TestRestaurant;
+  let supplierWallet: Keypair;
+  let flour: PublicKey;
+  const supplierId = 1;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    supplierWallet = await fundedKeypair();
+    flour = await addInventoryItem(restaurant, "flour", 0);
+
+    // Revenue the restaurant pays its supplier from
+    const bread = await addMenuItem(restaurant, "bread", 5_000_000);
+    await placeOrder(restaurant, await fundedKeypair(), [[bread, 1]]);
+
+    await program.methods
+      .restaurantAddSupplier({ id: bn(supplierId), name: "Mill", wallet: supplierWallet.publicKey, active: true })
+      .accountsPartial({ supplier: supplierPda(restaurant, supplierId), ...ownerAccounts(restaurant) })
+      .signers([restaurant.owner])
+      .rpc();
+  });
+
+  const createPurchaseOrder = async () => {
+    const id = randomId();
+    await program.methods
+      .restaurantCreatePurchaseOrder({
+        id: bn(id),
+        supplierId: bn(supplierId),
+        lines: [{ quantity: bn(10), unitCost: bn(100_000) }],
+      })
+      .accountsPartial({
+        purchaseOrder: purchaseOrderPda(restaurant, id),
+        supplier: supplierPda(restaurant, supplierId),
+        ...ownerAccounts(restaurant),
+      })
+      .remainingAccounts([writable(flour)])
+      .signers([restaurant.owner])
+      .rpc();
+    return id;
+  };
+
+  it("Receives a purchase order into stock and pays the supplier", async () => {
+    const id = await createPurchaseOrder();
+    const stockBefore = (await program.account.inventoryItem.fetch(flour)).stock.toNumber();
+
+    await receivePurchaseOrder(restaurant, id, 10, flour);
+
+    const item = await program.account.inventoryItem.fetch(flour);
+    expect(item.stock.toNumber()).to.equal(stockBefore + 10);
+    expect(item.lots[item.lots.length - 1].unitCost.toNumber()).to.equal(100_000);
+    expect((await program.account.purchaseOrder.fetch(purchaseOrderPda(restaurant, id))).status).to.have.property("received");
+
+    const supplierBefore = await balance(supplierWallet.publicKey);
+    await payPurchaseOrder(restaurant, id, supplierId, supplierWallet.publicKey);
+
+    expect(await balance(supplierWallet.publicKey)).to.equal(supplierBefore + 1_000_000);
+    expect((await program.account.purchaseOrder.fetch(purchaseOrderPda(restaurant, id))).paid.toNumber()).to.equal(1_000_000);
+  });
+
+  it("Rejects receiving more than was ordered", async () => {
+    const id = await createPurchaseOrder();
+
+    await expectError(receivePurchaseOrder(restaurant, id, 11, flour), "InvalidReceipt");
+  });
+
+  it("Rejects paying a purchase order twice", async () => {
+    const id = await createPurchaseOrder();
+    await receivePurchaseOrder(restaurant, id, 5, flour);
+    await payPurchaseOrder(restaurant, id, supplierId, supplierWallet.publicKey);
+
+    await expectError(payPurchaseOrder(restaurant, id, supplierId, supplierWallet.publicKey), "NothingToPay");
+  });
+});
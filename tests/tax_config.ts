@@ -0,0 +1,54 @@
+import { expect } from "chai";
+import {
+  addMenuItem, balance, createRestaurant, expectError, fundedKeypair, ownerAccounts, placeOrder, program,
+} from "./utils";
+
+// MenuCategoryType order: Combo, Side, Entree, Dessert, Beverage, Alcohol, Other
+const ENTREE = 2;
+const ALCOHOL = 5;
+
+describe("tax_config", () => {
+  it("Charges category tax and the service charge on top of the subtotal", async () => {
+    const restaurant = await createRestaurant();
+    await program.methods
+      .restaurantUpdateTaxConfig({
+        taxBps: 1000,
+        categoryTaxBps: [null, null, null, null, null, 2000, null],
+        serviceChargeBps: 500,
+      })
+      .accountsPartial(ownerAccounts(restaurant))
+      .signers([restaurant.owner])
+      .rpc();
+
+    const steak = await addMenuItem(restaurant, "steak", 1_000_000, [], ENTREE);
+    const wine = await addMenuItem(restaurant, "wine", 1_000_000, [], ALCOHOL);
+    const customer = await fundedKeypair();
+
+    const restaurantBefore = await balance(restaurant.restaurant);
+    const { order } = await placeOrder(restaurant, customer, [[steak, 1], [wine, 1]]);
+
+    const placed = await program.account.customerOrder.fetch(order);
+    expect(placed.subtotal.toNumber()).to.equal(2_000_000);
+    expect(placed.tax.toNumber()).to.equal(300_000);
+    expect(placed.serviceCharge.toNumber()).to.equal(100_000);
+    expect(placed.total.toNumber()).to.equal(2_400_000);
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore + 2_400_000);
+  });
+
+  it("Rejects a rate above 10000 basis points", async () => {
+    const restaurant = await createRestaurant();
+
+    await expectError(
+      program.methods
+        .restaurantUpdateTaxConfig({
+          taxBps: 1000,
+          categoryTaxBps: [null, null, null, null, null, 10_001, null],
+          serviceChargeBps: 0,
+        })
+        .accountsPartial(ownerAccounts(restaurant))
+        .signers([restaurant.owner])
+        .rpc(),
+      "InvalidRate",
+    );
+  });
+});
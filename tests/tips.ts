@@ -0,0 +1,211 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addEmployee, addMenuItem, balance, createRestaurant, EmployeeType, expectError, fundedKeypair, ownerAccounts, pda,
+  placeOrder, program, readonly, TestRestaurant, writable,
+} from "./utils";
+
+type TestEmployee = { keypair: Keypair; employee: PublicKey };
+
+const tipPoolPda = (restaurant: TestRestaurant) => pda(Buffer.from("tip_pool"), restaurant.restaurant.toBuffer());
+const shiftPda = (restaurant: TestRestaurant, employee: TestEmployee) =>
+  pda(Buffer.from("shift"), restaurant.restaurant.toBuffer(), employee.keypair.publicKey.toBuffer());
+
+async function configureTipPool(restaurant: TestRestaurant) {
+  await program.methods
+    .restaurantConfigureTipPool({ distribution: 0, roleWeights: [1, 1, 1, 1] })
+    .accountsPartial({
+      ...ownerAccounts(restaurant),
+      tipPool: tipPoolPda(restaurant),
+      currency: null,
+      tipVault: null,
+      tokenProgram: null,
+    })
+    .signers([restaurant.owner])
+    .rpc();
+}
+
+const clockIn = (restaurant: TestRestaurant, employee: TestEmployee) =>
+  program.methods
+    .employeeClockIn()
+    .accountsPartial({
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      shift: shiftPda(restaurant, employee),
+      tipPool: tipPoolPda(restaurant),
+    })
+    .signers([employee.keypair])
+    .rpc();
+
+const tallyTips = (restaurant: TestRestaurant, employees: TestEmployee[]) =>
+  program.methods
+    .restaurantTallyTips()
+    .accountsPartial({
+      signer: restaurant.owner.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: null,
+      tipPool: tipPoolPda(restaurant),
+    })
+    .remainingAccounts(
+      employees.flatMap((employee) => [readonly(employee.employee), writable(shiftPda(restaurant, employee))]),
+    )
+    .signers([restaurant.owner])
+    .rpc();
+
+const shareAccounts = (restaurant: TestRestaurant, employees: TestEmployee[]) =>
+  employees.flatMap((employee) => [
+    readonly(employee.employee),
+    writable(shiftPda(restaurant, employee)),
+    writable(employee.keypair.publicKey),
+  ]);
+
+const distributeTips = (restaurant: TestRestaurant, employees: TestEmployee[]) =>
+  program.methods
+    .restaurantDistributeTips()
+    .accountsPartial({
+      signer: restaurant.owner.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: null,
+      tipPool: tipPoolPda(restaurant),
+      currency: null,
+      tipVault: null,
+      tokenProgram: null,
+    })
+    .remainingAccounts(shareAccounts(restaurant, employees))
+    .signers([restaurant.owner])
+    .rpc();
+
+describe("tips", () => {
+  it("Splits an order tip equally between the on-shift employees", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const waiter = await addEmployee(restaurant, EmployeeType.TeamMember);
+    await clockIn(restaurant, cook);
+    await clockIn(restaurant, waiter);
+
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+    await placeOrder(restaurant, customer, [[bread, 1]], {
+      tip: 1_000_000,
+      accounts: { tipPool: tipPoolPda(restaurant) },
+    });
+    expect((await program.account.tipPool.fetch(tipPoolPda(restaurant))).balance.toNumber()).to.equal(1_000_000);
+
+    const cookBefore = await balance(cook.keypair.publicKey);
+    const waiterBefore = await balance(waiter.keypair.publicKey);
+    await tallyTips(restaurant, [cook, waiter]);
+    await distributeTips(restaurant, [cook, waiter]);
+
+    expect(await balance(cook.keypair.publicKey)).to.equal(cookBefore + 500_000);
+    expect(await balance(waiter.keypair.publicKey)).to.equal(waiterBefore + 500_000);
+    const pool = await program.account.tipPool.fetch(tipPoolPda(restaurant));
+    expect(pool.balance.toNumber()).to.equal(0);
+    expect(pool.totalDistributed.toNumber()).to.equal(1_000_000);
+    expect(pool.round.toNumber()).to.equal(2);
+  });
+
+  it("Distributes a round across several batches", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const staff: TestEmployee[] = [];
+    for (let i = 0; i < 4; i++) {
+      staff.push(await addEmployee(restaurant, EmployeeType.TeamMember));
+      await clockIn(restaurant, staff[i]);
+    }
+
+    const bread = await addMenuItem(restaurant, "bread", 1_000_000);
+    const customer = await fundedKeypair();
+    await placeOrder(restaurant, customer, [[bread, 1]], {
+      tip: 1_000_000,
+      accounts: { tipPool: tipPoolPda(restaurant) },
+    });
+
+    const before = await Promise.all(staff.map((employee) => balance(employee.keypair.publicKey)));
+    await tallyTips(restaurant, staff.slice(0, 2));
+    await tallyTips(restaurant, staff.slice(2));
+    await distributeTips(restaurant, staff.slice(0, 1));
+
+    // A tip collected mid-round waits for the next round
+    await placeOrder(restaurant, customer, [[bread, 1]], {
+      tip: 400_000,
+      accounts: { tipPool: tipPoolPda(restaurant) },
+    });
+    await distributeTips(restaurant, staff.slice(1));
+
+    for (let i = 0; i < staff.length; i++) {
+      expect(await balance(staff[i].keypair.publicKey)).to.equal(before[i] + 250_000);
+    }
+    const pool = await program.account.tipPool.fetch(tipPoolPda(restaurant));
+    expect(pool.balance.toNumber()).to.equal(400_000);
+    expect(pool.round.toNumber()).to.equal(2);
+    expect(pool.onShift).to.equal(4);
+  });
+
+  it("Rejects paying out before every on-shift employee is tallied", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const waiter = await addEmployee(restaurant, EmployeeType.TeamMember);
+    await clockIn(restaurant, cook);
+    await clockIn(restaurant, waiter);
+    await tallyTips(restaurant, [cook]);
+
+    await expectError(distributeTips(restaurant, [cook]), "MissingOnShiftEmployees");
+  });
+
+  it("Rejects tallying an employee twice in a round", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+    await clockIn(restaurant, cook);
+    await tallyTips(restaurant, [cook]);
+
+    await expectError(tallyTips(restaurant, [cook]), "AlreadyTallied");
+  });
+
+  it("Rejects paying an employee twice in a round", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+    const waiter = await addEmployee(restaurant, EmployeeType.TeamMember);
+    await clockIn(restaurant, cook);
+    await clockIn(restaurant, waiter);
+    await tallyTips(restaurant, [cook, waiter]);
+    await distributeTips(restaurant, [cook]);
+
+    await expectError(distributeTips(restaurant, [cook]), "NotTallied");
+    await expectError(tallyTips(restaurant, [waiter]), "DistributionInProgress");
+  });
+
+  it("Rejects clocking in twice", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+    await clockIn(restaurant, cook);
+
+    await expectError(clockIn(restaurant, cook), "AlreadyClockedIn");
+  });
+
+  it("Rejects clocking out without an open shift", async () => {
+    const restaurant = await createRestaurant();
+    await configureTipPool(restaurant);
+    const cook = await addEmployee(restaurant, EmployeeType.TeamMember);
+
+    await expectError(
+      program.methods
+        .employeeClockOut()
+        .accountsPartial({
+          signer: cook.keypair.publicKey,
+          restaurant: restaurant.restaurant,
+          employee: cook.employee,
+          shift: shiftPda(restaurant, cook),
+          tipPool: tipPoolPda(restaurant),
+        })
+        .signers([cook.keypair])
+        .rpc(),
+      "NotClockedIn",
+    );
+  });
+});
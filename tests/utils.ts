@@ -0,0 +1,367 @@
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorError, Program } from "@coral-xyz/anchor";
+import { HestiaProtocol } from "../target/types/hestia_protocol";
+import {
+  AccountMeta, Keypair, LAMPORTS_PER_SOL, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+
+anchor.setProvider(anchor.AnchorProvider.env());
+
+export const program = anchor.workspace.HestiaProtocol as Program<HestiaProtocol>;
+export const provider = anchor.getProvider() as anchor.AnchorProvider;
+export const wallet = anchor.Wallet.local();
+
+// Restaurants created by the helpers are paid in native SOL, the default Pubkey currency
+export const NATIVE_SOL = PublicKey.default;
+
+// CustomerOrder status values, as taken by restaurant_update_order
+export const Status = { Pending: 0, Completed: 1, Finalized: 2, Cancelled: 3, Accepted: 4, Preparing: 5, Ready: 6, Refunded: 7 };
+
+// EmployeeType values, as taken by restaurant_add_employee
+export const EmployeeType = { TeamMember: 0, TeamLeader: 1, Manager: 2, Director: 3 };
+
+export const pda = (...seeds: (Buffer | Uint8Array)[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+export const u64 = (value: number | anchor.BN) => new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+export const bn = (value: number) => new anchor.BN(value);
+export const randomId = () => Math.floor(Math.random() * 1_000_000_000);
+export const now = () => Math.floor(Date.now() / 1000);
+
+export const writable = (pubkey: PublicKey): AccountMeta => ({ pubkey, isSigner: false, isWritable: true });
+export const readonly = (pubkey: PublicKey): AccountMeta => ({ pubkey, isSigner: false, isWritable: false });
+
+export async function airdrop(to: PublicKey, lamports = 10 * LAMPORTS_PER_SOL) {
+  const signature = await provider.connection.requestAirdrop(to, lamports);
+  const latest = await provider.connection.getLatestBlockhash();
+  await provider.connection.confirmTransaction({ signature, ...latest }, "confirmed");
+}
+
+export async function fundedKeypair() {
+  const keypair = Keypair.generate();
+  await airdrop(keypair.publicKey);
+  return keypair;
+}
+
+export async function balance(account: PublicKey) {
+  return provider.connection.getBalance(account, "confirmed");
+}
+
+/// Fails unless `promise` is rejected with the program error `code`
+export async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (err) {
+    const error = err instanceof AnchorError ? err : AnchorError.parse(err.logs ?? []);
+    expect(error?.error.errorCode.code, err.toString()).to.equal(code);
+    return;
+  }
+  expect.fail(`expected the transaction to fail with ${code}`);
+}
+
+/// Initializes the protocol once per validator, the wallet must be the protocol admin
+export async function initProtocol() {
+  const protocol = pda(Buffer.from("protocol"));
+  if (await provider.connection.getAccountInfo(protocol)) {
+    return;
+  }
+  await program.methods.protocolInit().accountsPartial({ owner: wallet.publicKey }).rpc();
+}
+
+export type TestRestaurant = {
+  owner: Keypair;
+  adminProfile: PublicKey;
+  restaurant: PublicKey;
+};
+
+export async function createRestaurant(rewardPointsPerToken = 10, utcOffsetMinutes = 0): Promise<TestRestaurant> {
+  await initProtocol();
+
+  const owner = await fundedKeypair();
+  const adminProfile = pda(Buffer.from("admin"), owner.publicKey.toBuffer());
+  const restaurant = pda(Buffer.from("restaurant"), owner.publicKey.toBuffer());
+
+  await program.methods
+    .restaurantInitialize({
+      id: bn(randomId()),
+      restaurantType: 2,
+      name: "Hestia Diner",
+      symbol: "HST",
+      currency: NATIVE_SOL,
+      url: "https://hestia.example",
+      rewardPointsPerToken: bn(rewardPointsPerToken),
+      utcOffsetMinutes,
+      bump: 0,
+    })
+    .accountsPartial({ restaurantAdmin: owner.publicKey, adminProfile, restaurant })
+    .signers([owner])
+    .rpc();
+
+  return { owner, adminProfile, restaurant };
+}
+
+/// Accounts shared by the instructions signed by the restaurant admin
+export const ownerAccounts = (restaurant: TestRestaurant) => ({
+  restaurantAdmin: restaurant.owner.publicKey,
+  adminProfile: restaurant.adminProfile,
+  restaurant: restaurant.restaurant,
+});
+
+export async function addEmployee(restaurant: TestRestaurant, employeeType: number) {
+  const keypair = await fundedKeypair();
+  const employee = pda(Buffer.from("employee"), restaurant.restaurant.toBuffer(), keypair.publicKey.toBuffer());
+
+  await program.methods
+    .restaurantAddEmployee({
+      wallet: keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employeeType,
+      username: "employee",
+      bump: 0,
+    })
+    .accountsPartial({ employee, ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+
+  return { keypair, employee };
+}
+
+export const inventoryItemPda = (restaurant: TestRestaurant, sku: string) =>
+  pda(Buffer.from("inventory"), restaurant.restaurant.toBuffer(), Buffer.from(sku));
+
+export async function addInventoryItem(
+  restaurant: TestRestaurant,
+  sku: string,
+  stock: number,
+  options: { price?: number; reorderThreshold?: number; parLevel?: number; autoDeactivate?: boolean } = {},
+) {
+  const item = inventoryItemPda(restaurant, sku);
+
+  await program.methods
+    .restaurantAddInventoryItem({
+      sku,
+      category: 2,
+      name: `Inventory ${sku}`,
+      price: bn(options.price ?? 100),
+      stock: bn(stock),
+      reorderThreshold: bn(options.reorderThreshold ?? 0),
+      parLevel: bn(options.parLevel ?? options.reorderThreshold ?? 0),
+      autoDeactivate: options.autoDeactivate ?? false,
+      initialized: false,
+    })
+    .accountsPartial({ item, ...ownerAccounts(restaurant) })
+    .signers([restaurant.owner])
+    .rpc();
+
+  return item;
+}
+
+export type TestMenuItem = {
+  sku: string;
+  menuItem: PublicKey;
+  ingredientList: PublicKey;
+  ingredients: PublicKey[];
+  price: number;
+};
+
+export async function addMenuItem(
+  restaurant: TestRestaurant,
+  sku: string,
+  price: number,
+  ingredients: [PublicKey, number][] = [],
+  category = 2,
+): Promise<TestMenuItem> {
+  const menuItem = pda(Buffer.from("menu_item"), restaurant.restaurant.toBuffer(), Buffer.from(sku));
+  const ingredientList = pda(Buffer.from("ingredient_list"), menuItem.toBuffer());
+
+  await program.methods
+    .restaurantAddMenuItem({
+      sku,
+      category,
+      name: `Menu ${sku}`,
+      price: bn(price),
+      description: "",
+      ingredients: ingredients.map(([item, quantity]) => [item, bn(quantity)]),
+      active: true,
+    })
+    .accountsPartial({
+      menuItem,
+      ingredientList,
+      menu: pda(Buffer.from("menu"), restaurant.restaurant.toBuffer()),
+      ...ownerAccounts(restaurant),
+    })
+    .signers([restaurant.owner])
+    .rpc();
+
+  return { sku, menuItem, ingredientList, ingredients: ingredients.map(([item]) => item), price };
+}
+
+/// Remaining accounts of an order line for a menu item without schedule, modifiers or combo
+export const lineAccounts = (item: TestMenuItem): AccountMeta[] => [
+  readonly(item.menuItem),
+  readonly(item.ingredientList),
+  ...item.ingredients.map(writable),
+];
+
+export const orderPda = (restaurant: TestRestaurant, orderId: number) =>
+  pda(Buffer.from("order"), restaurant.restaurant.toBuffer(), u64(orderId));
+
+export const customerPda = (restaurant: TestRestaurant, customer: PublicKey) =>
+  pda(Buffer.from("customer"), restaurant.restaurant.toBuffer(), customer.toBuffer());
+
+export type OrderOptions = {
+  orderId?: number;
+  customer?: PublicKey; // defaults to the paying wallet
+  total?: number;
+  tip?: number;
+  escrow?: boolean;
+  splitShares?: number[];
+  promoCode?: string;
+  modifiers?: { group: number; option: number }[][]; // per order line
+  substitutions?: { slot: number; substitute: number }[][]; // per order line
+  remainingAccounts?: AccountMeta[];
+  accounts?: Record<string, PublicKey | null>;
+  preInstructions?: TransactionInstruction[];
+};
+
+/// Places an order paid in native SOL by `customer` and returns its id and address
+export async function placeOrder(
+  restaurant: TestRestaurant,
+  customer: Keypair,
+  lines: [TestMenuItem, number][],
+  options: OrderOptions = {},
+) {
+  const orderId = options.orderId ?? randomId();
+  const order = orderPda(restaurant, orderId);
+  const total = options.total ?? lines.reduce((sum, [item, quantity]) => sum + item.price * quantity, 0);
+
+  await program.methods
+    .restaurantAddOrder({
+      orderId: bn(orderId),
+      customer: options.customer ?? customer.publicKey,
+      customerName: "Customer",
+      lines: lines.map(([item, quantity], index) => ({
+        menuItem: item.menuItem,
+        quantity,
+        modifiers: options.modifiers?.[index] ?? [],
+        substitutions: options.substitutions?.[index] ?? [],
+        note: null,
+      })),
+      total: bn(total),
+      tip: bn(options.tip ?? 0),
+      status: Status.Pending,
+      createdAt: bn(now()),
+      updatedAt: null,
+      useReward: false,
+      promoCode: options.promoCode ?? null,
+      escrow: options.escrow ?? false,
+      splitShares: (options.splitShares ?? []).map(bn),
+      bump: 0,
+    })
+    .accountsPartial({
+      order,
+      customer: customerPda(restaurant, customer.publicKey),
+      signer: customer.publicKey,
+      restaurant: restaurant.restaurant,
+      currency: null,
+      signerAta: null,
+      restaurantAta: null,
+      escrow: null,
+      receipt: null,
+      paymentConfig: null,
+      promotion: null,
+      promotionRedemption: null,
+      tipPool: null,
+      tipVault: null,
+      reward: null,
+      voucher: null,
+      customerVoucher: null,
+      menuItem: null,
+      tokenProgram: null,
+      mplCoreProgram: null,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      ...options.accounts,
+    })
+    .remainingAccounts(options.remainingAccounts ?? lines.flatMap(([item]) => lineAccounts(item)))
+    .preInstructions(options.preInstructions ?? [])
+    .signers([customer])
+    .rpc();
+
+  return { orderId, order };
+}
+
+/// Moves an order to `status`, signed by an employee of the restaurant
+export async function updateOrder(
+  restaurant: TestRestaurant,
+  employee: { keypair: Keypair; employee: PublicKey },
+  orderId: number,
+  status: number,
+  accounts: Record<string, PublicKey | null> = {},
+) {
+  return program.methods
+    .restaurantUpdateOrder({ orderId: bn(orderId), status })
+    .accountsPartial({
+      order: orderPda(restaurant, orderId),
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      currency: null,
+      escrow: null,
+      customerWallet: null,
+      restaurantAta: null,
+      tokenProgram: null,
+      ...accounts,
+    })
+    .signers([employee.keypair])
+    .rpc();
+}
+
+/// Moves a Pending order to Completed through every intermediate status
+export async function completeOrder(
+  restaurant: TestRestaurant,
+  employee: { keypair: Keypair; employee: PublicKey },
+  orderId: number,
+) {
+  for (const status of [Status.Accepted, Status.Preparing, Status.Ready, Status.Completed]) {
+    await updateOrder(restaurant, employee, orderId, status);
+  }
+}
+
+/// Writable InventoryItem accounts the order took stock from, in CustomerOrder.stock_taken order
+export const stockTakenAccounts = async (order: PublicKey): Promise<AccountMeta[]> =>
+  (await program.account.customerOrder.fetch(order)).stockTaken.map((taken) => writable(taken.inventoryItem));
+
+/// Cancels a native SOL order, signed by the customer, the restaurant admin or an employee (passed in `accounts`)
+export async function cancelOrder(
+  restaurant: TestRestaurant,
+  signer: Keypair,
+  orderId: number,
+  customer: PublicKey,
+  options: { accounts?: Record<string, PublicKey | null>; remainingAccounts?: AccountMeta[] } = {},
+) {
+  const order = orderPda(restaurant, orderId);
+
+  return program.methods
+    .restaurantCancelOrder({ orderId: bn(orderId) })
+    .accountsPartial({
+      order,
+      customer: customerPda(restaurant, customer),
+      restaurant: restaurant.restaurant,
+      signer: signer.publicKey,
+      adminProfile: restaurant.adminProfile,
+      employee: null,
+      currency: null,
+      restaurantAta: null,
+      customerAta: null,
+      customerWallet: customer,
+      escrow: null,
+      promotion: null,
+      promotionRedemption: null,
+      tipPool: null,
+      tipVault: null,
+      tokenProgram: null,
+      ...options.accounts,
+    })
+    .remainingAccounts(options.remainingAccounts ?? (await stockTakenAccounts(order)))
+    .signers([signer])
+    .rpc();
+}
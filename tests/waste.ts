@@ -0,0 +1,66 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  addEmployee, addInventoryItem, bn, createRestaurant, EmployeeType, expectError, inventoryItemPda, ownerAccounts, pda,
+  program, TestRestaurant,
+} from "./utils";
+
+// WasteReason order: Spoiled, Dropped, Comped, Theft, Expired
+const DROPPED = 1;
+
+const wasteLogPda = (restaurant: TestRestaurant) => pda(Buffer.from("waste_log"), restaurant.restaurant.toBuffer());
+
+const recordWaste = (
+  restaurant: TestRestaurant,
+  employee: { keypair: Keypair; employee: PublicKey },
+  sku: string,
+  quantity: number,
+) =>
+  program.methods
+    .restaurantRecordWaste({ sku, quantity: bn(quantity), reason: DROPPED })
+    .accountsPartial({
+      signer: employee.keypair.publicKey,
+      restaurant: restaurant.restaurant,
+      employee: employee.employee,
+      item: inventoryItemPda(restaurant, sku),
+      wasteLog: wasteLogPda(restaurant),
+    })
+    .signers([employee.keypair])
+    .rpc();
+
+describe("waste", () => {
+  let restaurant: TestRestaurant;
+
+  before(async () => {
+    restaurant = await createRestaurant();
+    await addInventoryItem(restaurant, "eggs", 0);
+    await program.methods
+      .restaurantReceiveInventoryLot({ sku: "eggs", quantity: bn(5), unitCost: bn(150), expiresAt: null })
+      .accountsPartial({ item: inventoryItemPda(restaurant, "eggs"), ...ownerAccounts(restaurant) })
+      .signers([restaurant.owner])
+      .rpc();
+  });
+
+  it("Records waste at lot cost in the restaurant's waste log", async () => {
+    const leader = await addEmployee(restaurant, EmployeeType.TeamLeader);
+    await recordWaste(restaurant, leader, "eggs", 2);
+
+    expect((await program.account.inventoryItem.fetch(inventoryItemPda(restaurant, "eggs"))).stock.toNumber()).to.equal(3);
+    const wasteLog = await program.account.wasteLog.fetch(wasteLogPda(restaurant));
+    expect(wasteLog.totalCost.toNumber()).to.equal(300);
+    expect(wasteLog.costByReason[DROPPED].toNumber()).to.equal(300);
+    expect(wasteLog.entriesByReason[DROPPED].toNumber()).to.equal(1);
+  });
+
+  it("Rejects waste recorded by a team member", async () => {
+    const member = await addEmployee(restaurant, EmployeeType.TeamMember);
+
+    await expectError(recordWaste(restaurant, member, "eggs", 1), "UnauthorizedWaste");
+  });
+
+  it("Rejects wasting more than the stock on hand", async () => {
+    const leader = await addEmployee(restaurant, EmployeeType.TeamLeader);
+
+    await expectError(recordWaste(restaurant, leader, "eggs", 100), "InsufficientStock");
+  });
+});
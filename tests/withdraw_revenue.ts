@@ -0,0 +1,43 @@
+import { expect } from "chai";
+import {
+  addMenuItem, balance, bn, createRestaurant, expectError, fundedKeypair, ownerAccounts, placeOrder, program,
+} from "./utils";
+
+describe("withdraw_revenue", () => {
+  it("Owner withdraws the native SOL revenue of an order", async () => {
+    const restaurant = await createRestaurant();
+    const bread = await addMenuItem(restaurant, "bread", 5_000_000);
+    const customer = await fundedKeypair();
+
+    const restaurantBefore = await balance(restaurant.restaurant);
+    const { order } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    const paid = (await program.account.customerOrder.fetch(order)).paid.toNumber();
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore + paid);
+
+    await program.methods
+      .restaurantWithdrawRevenue(bn(paid))
+      .accountsPartial(ownerAccounts(restaurant))
+      .signers([restaurant.owner])
+      .rpc();
+
+    expect(await balance(restaurant.restaurant)).to.equal(restaurantBefore);
+  });
+
+  it("Rejects withdrawing into the restaurant's rent-exempt minimum", async () => {
+    const restaurant = await createRestaurant();
+    const bread = await addMenuItem(restaurant, "bread", 5_000_000);
+    const customer = await fundedKeypair();
+
+    const { order } = await placeOrder(restaurant, customer, [[bread, 1]]);
+    const paid = (await program.account.customerOrder.fetch(order)).paid.toNumber();
+
+    await expectError(
+      program.methods
+        .restaurantWithdrawRevenue(bn(paid + 1))
+        .accountsPartial(ownerAccounts(restaurant))
+        .signers([restaurant.owner])
+        .rpc(),
+      "InsufficientRevenue",
+    );
+  });
+});